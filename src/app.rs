@@ -14,37 +14,38 @@ pub struct BrewingCalcApp {
     base: base::Base,
     bjcp_indexer: bjcp_style_index::BJCPStyleIndex,
     ingredients_indexer: ingredients_index::IngredientsIndex,
+    inventory: inventory::Inventory,
     equilibrium_pressure: equilibrium_pressure::EquilibriumPressure,
     temperature_after_mix: temperature_after_mix::TemperatureAfterMix,
+    strike_temperature: strike_temperature::StrikeTemperature,
     yeast: yeast::Yeast,
     fermentecibles: fermentecibles::Fermentecibles,
+    equipment: equipment::EquipmentProfiles,
     water: water::Water,
+    mash_schedule: mash_schedule::MashSchedule,
     hops: hops::Hops,
+    style_conformance: style_conformance::StyleConformance,
+    cost: cost::Cost,
 }
 
 impl Default for BrewingCalcApp {
     fn default() -> Self {
-        #[cfg(not(target_arch = "wasm32"))]
-        let beer_styles =
-            modules::JsonParser::<bjcp_style_index::BeerStyle>::new(bjcp_style_index::JSON_PATH)
-                .parse_json();
-
-        #[cfg(target_arch = "wasm32")]
-        let beer_styles = modules::WasmJsonParser::<bjcp_style_index::BeerStyle>::new(
-            bjcp_style_index::BJCPStyleIndex::parse_file(),
-        )
-        .parse_json();
-
         Self {
             base: base::Base::new(),
-            bjcp_indexer: bjcp_style_index::BJCPStyleIndex::new(beer_styles),
+            bjcp_indexer: bjcp_style_index::BJCPStyleIndex::new(),
             ingredients_indexer: ingredients_index::IngredientsIndex::new(),
+            inventory: inventory::Inventory::new(),
             equilibrium_pressure: equilibrium_pressure::EquilibriumPressure::new(),
             temperature_after_mix: temperature_after_mix::TemperatureAfterMix::new(),
+            strike_temperature: strike_temperature::StrikeTemperature::new(),
             yeast: yeast::Yeast::new(),
             fermentecibles: fermentecibles::Fermentecibles::new(),
+            equipment: equipment::EquipmentProfiles::new(),
             water: water::Water::new(),
+            mash_schedule: mash_schedule::MashSchedule::new(),
             hops: hops::Hops::new(),
+            style_conformance: style_conformance::StyleConformance::new(),
+            cost: cost::Cost::new(),
         }
     }
 }
@@ -57,11 +58,24 @@ impl BrewingCalcApp {
 
         // Load previous app state (if any).
         // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return get_value(storage, APP_KEY).unwrap_or_default();
-        }
+        let mut app: Self = match cc.storage {
+            Some(storage) => get_value(storage, APP_KEY).unwrap_or_default(),
+            None => Default::default(),
+        };
+
+        app.bjcp_indexer.load_guides();
+        app.yeast.yeasts_index = app.ingredients_indexer.yeasts().to_vec();
 
-        Default::default()
+        app
+    }
+
+    /// Ingredient weights of the current recipe
+    ///
+    fn recipe_uses(&self) -> Vec<ingredients_index::IngredientUse> {
+        let mut uses = self.fermentecibles.ingredient_uses();
+        uses.extend(self.hops.ingredient_uses());
+        uses.extend(self.yeast.ingredient_uses());
+        uses
     }
 }
 
@@ -95,13 +109,27 @@ impl App for BrewingCalcApp {
             });
         });
 
+        self.bjcp_indexer.recipe_vitals = bjcp_style_index::RecipeVitals {
+            og: math::convert_plato_to_sg(self.base.original_gravity),
+            fg: math::convert_plato_to_sg(self.base.final_gravity),
+            abv: self.base.abv,
+            ibu: self.base.ibu,
+            srm: math::convert_ebc_to_srm(self.base.ebc),
+        };
+
+        self.inventory.recipe_uses = self.recipe_uses();
+        self.strike_temperature.grain_weight = self.fermentecibles.grain_weight;
+        self.strike_temperature.mash_water_vol = self.water.mash_water_vol();
+
         SidePanel::right("right_panel").show(ctx, |ui| self.bjcp_indexer.show(ui));
         SidePanel::left("left_panel").show(ctx, |ui| {
             ui.heading("Outils");
 
             self.equilibrium_pressure.show(ui);
             self.temperature_after_mix.show(ui);
-            self.ingredients_indexer.show(ui)
+            self.strike_temperature.show(ui);
+            self.ingredients_indexer.show(ui);
+            self.inventory.show(ui, &self.ingredients_indexer);
         });
 
         // Add a lot of widgets here.
@@ -116,18 +144,76 @@ impl App for BrewingCalcApp {
 
                 self.base.ebc = self.fermentecibles.ebc;
                 self.base.yeast_attenuation = self.yeast.max_attenuation;
+                self.base.guide_style = self
+                    .bjcp_indexer
+                    .recipe_style()
+                    .map(|style| style.reference());
 
                 self.base.show(ui);
 
                 ui.add_space(DEFAULT_SPACING);
 
+                self.style_conformance.style_name = self
+                    .bjcp_indexer
+                    .recipe_style()
+                    .map(|style| style.reference());
+                self.style_conformance.ranges = self
+                    .bjcp_indexer
+                    .recipe_style()
+                    .map(|style| *style.ranges())
+                    .unwrap_or_default();
+                self.style_conformance.original_gravity = self.base.original_gravity;
+                self.style_conformance.final_gravity = self.base.final_gravity;
+                self.style_conformance.abv = self.base.abv;
+                self.style_conformance.ibu = self.base.ibu;
+                self.style_conformance.ebc = self.base.ebc;
+
+                self.style_conformance.show(ui);
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.equipment.show(ui);
+
+                if let Some(efficiency) = self.equipment.take_efficiency_changed() {
+                    self.base.lauter_efficiency = efficiency;
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.water.equipment = self.equipment.equipment();
                 self.water.batch_size = self.base.batch_size;
-                self.water.grain_weight = self.fermentecibles.total_weight;
+                self.water.original_gravity = self.base.original_gravity;
+                self.water.final_gravity = self.base.final_gravity;
+                self.water.grain_weight = self.fermentecibles.grain_weight;
+                self.water.malts = self.fermentecibles.mash_malts();
+                self.water.mash_potential_extract = self.fermentecibles.mash_potential_extract();
+                self.water.boil_potential_extract = self.fermentecibles.boil_potential_extract();
+                self.water.mash_efficiency = math::compute_mash_efficiency(
+                    self.base.conversion_efficiency,
+                    self.base.lauter_efficiency,
+                );
+                self.water.infusion_water_coefficients =
+                    self.mash_schedule.infusion_water_coefficients();
 
                 self.water.show(ui);
 
+                if let Some(efficiency) = self.water.take_efficiency_changed() {
+                    self.base.lauter_efficiency = efficiency;
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.mash_schedule.grain_weight = self.fermentecibles.grain_weight;
+                self.mash_schedule.mash_water_vol = self.water.mash_water_vol();
+
+                self.mash_schedule.show(ui);
+
                 ui.add_space(DEFAULT_SPACING);
 
+                if self.ingredients_indexer.take_yeasts_changed() {
+                    self.yeast.yeasts_index = self.ingredients_indexer.yeasts().to_vec();
+                }
+
                 self.yeast.original_gravity = self.base.original_gravity;
                 self.yeast.batch_size = self.base.batch_size;
 
@@ -137,7 +223,9 @@ impl App for BrewingCalcApp {
 
                 self.fermentecibles.batch_size = self.base.batch_size;
                 self.fermentecibles.original_gravity = self.base.original_gravity;
-                self.fermentecibles.efficiency = self.base.efficiency;
+                self.fermentecibles.conversion_efficiency = self.base.conversion_efficiency;
+                self.fermentecibles.lauter_efficiency = self.base.lauter_efficiency;
+                self.fermentecibles.kettle_efficiency = self.water.kettle_efficiency();
 
                 self.fermentecibles.show(ui);
 
@@ -147,6 +235,14 @@ impl App for BrewingCalcApp {
                 self.hops.batch_size = self.base.batch_size;
                 self.hops.ibu = self.base.ibu;
                 self.hops.show(ui);
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.cost.packaged_volume = self.water.packaged_vol();
+                self.cost.water_volume = self.water.total_water_vol();
+                self.cost.recipe_uses = self.recipe_uses();
+
+                self.cost.show(ui);
             });
         });
     }
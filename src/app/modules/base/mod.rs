@@ -7,30 +7,54 @@ use serde::{Deserialize, Serialize};
 #[derive(Deserialize, Serialize, Default)]
 pub struct Base {
     name: String,
+    /// Free text, used when no style is picked in a guide
     style: String,
+    /// Style picked in the style guides
+    #[serde(skip)]
+    pub guide_style: Option<String>,
     bugu: f32,
     pub ebc: u8,
     pub abv: f32,
     pub ibu: f32,
     pub original_gravity: f32,
     pub final_gravity: f32,
-    pub efficiency: u8,
+    /// Overall efficiency of older saves, migrated to the lauter efficiency
+    #[serde(default, skip_serializing)]
+    efficiency: Option<u8>,
+    /// %
+    #[serde(default)]
+    pub lauter_efficiency: u8,
+    /// %
+    #[serde(default = "default_conversion_efficiency")]
+    pub conversion_efficiency: u8,
     pub batch_size: u16,
     pub yeast_attenuation: u8,
 }
 
+fn default_conversion_efficiency() -> u8 {
+    95
+}
+
 impl super::AppModule for Base {
     fn new() -> Self {
         Self {
             ibu: 20.,
             original_gravity: 12.,
-            efficiency: 75,
+            lauter_efficiency: 75,
+            conversion_efficiency: default_conversion_efficiency(),
             batch_size: 30,
             ..Default::default()
         }
     }
 
     fn show(&mut self, ui: &mut Ui) {
+        // The overall efficiency was conversion and lauter together
+        if let Some(efficiency) = self.efficiency.take() {
+            self.lauter_efficiency = (efficiency as f32 * 100. / self.conversion_efficiency as f32)
+                .round()
+                .min(100.) as u8;
+        }
+
         self.final_gravity =
             math::compute_final_gravity(self.original_gravity, self.yeast_attenuation as f32);
         self.abv = math::compute_abv(self.original_gravity, self.final_gravity);
@@ -42,10 +66,18 @@ impl super::AppModule for Base {
 
         ui.add_space(DEFAULT_SPACING);
 
-        ui.horizontal(|ui| {
-            ui.label("Style : ");
-            ui.text_edit_singleline(&mut self.style);
-        });
+        match &self.guide_style {
+            Some(guide_style) => {
+                ui.label(format!("Style : {guide_style}"));
+            }
+            None => {
+                ui.horizontal(|ui| {
+                    ui.label("Style : ");
+                    ui.text_edit_singleline(&mut self.style)
+                        .on_hover_text("Ou à choisir dans la liste des guides de styles");
+                });
+            }
+        }
 
         ui.add_space(DEFAULT_SPACING);
 
@@ -94,15 +126,25 @@ impl super::AppModule for Base {
         ui.add_space(DEFAULT_SPACING);
 
         ui.horizontal(|ui| {
-            ui.label("Efficacité (%): ");
-            ui.add(Slider::new(&mut self.efficiency, 0..=100));
+            ui.label("Efficacité de conversion (%): ");
+            ui.add(Slider::new(&mut self.conversion_efficiency, 0..=100))
+                .on_hover_text("Part de l'extrait potentiel convertie dans la maische");
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.horizontal(|ui| {
+            ui.label("Efficacité de filtration (%): ");
+            ui.add(Slider::new(&mut self.lauter_efficiency, 0..=100))
+                .on_hover_text("Part de l'extrait converti récupérée dans la cuve d'ébullition");
         });
 
         ui.add_space(DEFAULT_SPACING);
 
         ui.horizontal(|ui| {
-            ui.label("Volume (L): ");
-            ui.add(Slider::new(&mut self.batch_size, 0..=30000));
+            ui.label("Volume dans le fermenteur (L): ");
+            ui.add(Slider::new(&mut self.batch_size, 0..=30000))
+                .on_hover_text("Moût refroidi transféré au fermenteur, avant les pertes de fermentation et de conditionnement");
         });
     }
 }
@@ -0,0 +1,127 @@
+use super::{tags, BeerStyle, RangeError, StyleRanges};
+use serde::{Deserialize, Serialize};
+
+/// A style guide file bundled with the app
+/// Every file shares the `beer_styles.json` schema
+pub struct StyleGuideSource {
+    pub name: &'static str,
+    pub edition: &'static str,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub path: &'static str,
+    #[cfg(target_arch = "wasm32")]
+    pub file: &'static [u8],
+}
+
+/// Registry of the available style guides, the first one is selected by default
+/// To add a guide, bundle its JSON file next to `beer_styles.json` and list it here,
+/// or import it from the style index at runtime
+pub const STYLE_GUIDES: &[StyleGuideSource] = &[StyleGuideSource {
+    name: "BJCP",
+    edition: "2021",
+    #[cfg(not(target_arch = "wasm32"))]
+    path: super::JSON_PATH,
+    #[cfg(target_arch = "wasm32")]
+    file: super::JSON_FILE,
+}];
+
+/// A guide pasted by the user in the `beer_styles.json` schema
+/// Kept as JSON and loaded again along with the bundled guides
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct ImportedGuide {
+    pub name: String,
+    pub edition: String,
+    pub json: String,
+}
+
+/// An imported guide that could not be loaded, it stays saved until deleted
+pub struct GuideLoadError {
+    pub imported: usize,
+    pub message: String,
+}
+
+/// Which guide and edition a style comes from
+#[derive(Deserialize, Serialize, Default, Clone, PartialEq)]
+pub struct StyleGuideRef {
+    pub name: String,
+    pub edition: String,
+}
+
+impl std::fmt::Display for StyleGuideRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name, self.edition)
+    }
+}
+
+pub struct StyleGuide {
+    pub reference: StyleGuideRef,
+    /// Index in the imported guides, none for the bundled ones
+    pub imported: Option<usize>,
+    pub beer_styles: Vec<BeerStyle>,
+    pub range_errors: Vec<RangeError>,
+}
+
+impl StyleGuide {
+    pub fn load(source: &StyleGuideSource) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let beer_styles =
+            crate::app::modules::JsonParser::<BeerStyle>::new(source.path).parse_json();
+
+        #[cfg(target_arch = "wasm32")]
+        let beer_styles = crate::app::modules::WasmJsonParser::<BeerStyle>::new(
+            String::from_utf8_lossy(source.file).to_string(),
+        )
+        .parse_json();
+
+        Self::new(
+            StyleGuideRef {
+                name: source.name.into(),
+                edition: source.edition.into(),
+            },
+            beer_styles,
+        )
+    }
+
+    pub fn import(guide: &ImportedGuide) -> Result<Self, serde_json::Error> {
+        let beer_styles = serde_json::from_str(&guide.json)?;
+
+        Ok(Self::new(
+            StyleGuideRef {
+                name: guide.name.trim().into(),
+                edition: guide.edition.trim().into(),
+            },
+            beer_styles,
+        ))
+    }
+
+    fn new(reference: StyleGuideRef, mut beer_styles: Vec<BeerStyle>) -> Self {
+        let mut range_errors = vec![];
+
+        for style in &mut beer_styles {
+            let (ranges, errors) = StyleRanges::parse(style);
+
+            style.ranges = ranges;
+            style.guide = reference.clone();
+            style.tag_set = tags::parse_tags(&style.tags);
+            range_errors.extend(errors);
+        }
+
+        tags::unify_tags(&mut beer_styles);
+
+        for error in &range_errors {
+            log::warn!(
+                "Invalid {} range in style `{}` ({reference}): {}",
+                error.field,
+                error.style,
+                error.reason
+            );
+        }
+
+        Self {
+            reference,
+            imported: None,
+            beer_styles,
+            range_errors,
+        }
+    }
+}
@@ -1,9 +1,19 @@
+mod guides;
+mod ranges;
+mod recommender;
+mod search;
+mod tags;
+
+pub use guides::{StyleGuideRef, STYLE_GUIDES};
+pub use ranges::{RangeError, StyleRange, StyleRanges};
+
 use crate::app::modules::math::{convert_sg_to_plato, convert_srm_to_ebc};
 use crate::app::modules::ui_defaults::*;
 
 use eframe::*;
 use egui::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub const JSON_PATH: &str = "src/app/modules/bjcp_style_index/assets/beer_styles.json";
@@ -11,17 +21,48 @@ pub const JSON_PATH: &str = "src/app/modules/bjcp_style_index/assets/beer_styles
 #[cfg(target_arch = "wasm32")]
 pub const JSON_FILE: &[u8; 395720] = include_bytes!("assets/beer_styles.json");
 
-#[derive(Deserialize, Serialize)]
+const RECOMMENDATIONS_COUNT: usize = 10;
+
+#[derive(Deserialize, Serialize, Default)]
 #[serde(default)]
 pub struct BJCPStyleIndex {
-    beer_styles: Vec<BeerStyle>,
-    prompt: String,
+    #[serde(skip)]
+    guides: Vec<guides::StyleGuide>,
+    #[serde(skip)]
+    guide_index: usize,
+    /// Guide picked by the user, found again by reference once the guides are loaded
+    selected_guide: StyleGuideRef,
+    #[serde(skip)]
+    load_errors: Vec<guides::GuideLoadError>,
+    /// Selected guide was missing at load time
+    #[serde(skip)]
+    missing_guide: Option<StyleGuideRef>,
+    /// Guides loaded at runtime, listed after the bundled ones
+    imported_guides: Vec<guides::ImportedGuide>,
+    #[serde(skip)]
+    import: guides::ImportedGuide,
+    #[serde(skip)]
+    import_error: Option<String>,
+    query: search::StyleQuery,
     result: Vec<BeerStyle>,
+    recipe_style: Option<BeerStyle>,
+    #[serde(skip)]
+    pub recipe_vitals: RecipeVitals,
+}
+
+/// Recipe values in the styles' units, gravities in SG and color in SRM
+#[derive(Clone, Copy, Default)]
+pub struct RecipeVitals {
+    pub og: f32,
+    pub fg: f32,
+    pub abv: f32,
+    pub ibu: f32,
+    pub srm: f32,
 }
 
 #[derive(Deserialize, Serialize, Default, Clone)]
 pub struct BeerStyle {
-    name: String,
+    pub name: String,
     number: Option<String>,
     category: Option<String>,
     categorynumber: Option<String>,
@@ -49,47 +90,434 @@ pub struct BeerStyle {
     srmmax: Option<String>,
     commercialexamples: Option<String>,
     tags: Option<String>,
+    #[serde(default)]
+    pub guide: StyleGuideRef,
+    #[serde(skip)]
+    ranges: StyleRanges,
+    #[serde(skip)]
+    tag_set: BTreeSet<String>,
     #[serde(skip)]
     opened: bool,
 }
 
-impl Default for BJCPStyleIndex {
-    fn default() -> Self {
-        Self {
-            beer_styles: vec![],
-            prompt: "".into(),
-            result: vec![],
-        }
+impl BeerStyle {
+    pub fn ranges(&self) -> &StyleRanges {
+        &self.ranges
+    }
+
+    pub fn tags(&self) -> &BTreeSet<String> {
+        &self.tag_set
+    }
+
+    /// Style name along with the guide and edition it came from
+    ///
+    pub fn reference(&self) -> String {
+        format!("{} ({})", self.name, self.guide)
     }
 }
 
 impl BJCPStyleIndex {
-    #[cfg(target_arch = "wasm32")]
-    pub fn parse_file() -> String {
-        String::from_utf8_lossy(&JSON_FILE[..]).to_string()
+    pub fn new() -> Self {
+        let mut index = Self {
+            ..Default::default()
+        };
+
+        index.load_guides();
+
+        index
     }
 
-    pub fn new(beer_styles: Vec<BeerStyle>) -> Self {
-        Self {
-            beer_styles,
-            ..Default::default()
+    /// Guides aren't persisted, they are loaded from the registry's files
+    /// when the index is created or restored from app storage
+    ///
+    pub fn load_guides(&mut self) {
+        if !self.guides.is_empty() {
+            return;
+        }
+
+        self.guides = STYLE_GUIDES.iter().map(guides::StyleGuide::load).collect();
+        self.load_errors.clear();
+
+        // Guides that no longer parse stay saved, they are only left out of the list
+        for (index, imported) in self.imported_guides.iter().enumerate() {
+            match guides::StyleGuide::import(imported) {
+                Ok(mut guide) => {
+                    guide.imported = Some(index);
+                    self.guides.push(guide);
+                }
+                Err(error) => {
+                    log::warn!(
+                        "Unable to load style guide {} {}: {error}",
+                        imported.name,
+                        imported.edition
+                    );
+                    self.load_errors.push(guides::GuideLoadError {
+                        imported: index,
+                        message: format!("{} {} : {error}", imported.name, imported.edition),
+                    });
+                }
+            }
+        }
+
+        self.guide_index = match self
+            .guides
+            .iter()
+            .position(|guide| guide.reference == self.selected_guide)
+        {
+            Some(index) => index,
+            None => {
+                if !self.selected_guide.name.is_empty() {
+                    self.missing_guide = Some(self.selected_guide.clone());
+                }
+                0
+            }
+        };
+
+        if let Some(guide) = self.guides.get(self.guide_index) {
+            self.selected_guide = guide.reference.clone();
+        }
+
+        if let Some(guide) = self.guides.get(self.guide_index) {
+            self.result = search::search_styles(&self.query, &guide.beer_styles);
+        }
+
+        if let Some(recipe_style) = &mut self.recipe_style {
+            recipe_style.ranges = StyleRanges::parse(recipe_style).0;
         }
     }
 
+    /// Index of the guide once imported
+    ///
+    fn import_guide_ui(&mut self, ui: &mut Ui) -> Option<usize> {
+        let import = &mut self.import;
+
+        ui.horizontal(|ui| {
+            ui.label("Nom : ");
+            ui.add(TextEdit::singleline(&mut import.name).desired_width(100.));
+            ui.label("Édition : ");
+            ui.add(TextEdit::singleline(&mut import.edition).desired_width(60.));
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.label("JSON au format de beer_styles.json : ");
+        ScrollArea::vertical()
+            .id_salt("guide-import")
+            .max_height(150.)
+            .show(ui, |ui| {
+                ui.add(
+                    TextEdit::multiline(&mut import.json)
+                        .code_editor()
+                        .desired_rows(6),
+                );
+            });
+
+        if let Some(error) = &self.import_error {
+            ui.colored_label(ERROR_COLOR, error);
+        }
+
+        let ready = !import.name.trim().is_empty() && !import.json.trim().is_empty();
+
+        if !ui.add_enabled(ready, Button::new("Importer")).clicked() {
+            return None;
+        }
+
+        match guides::StyleGuide::import(import) {
+            Ok(mut guide) => {
+                self.import_error = None;
+                guide.imported = Some(self.imported_guides.len());
+                self.imported_guides.push(std::mem::take(import));
+                self.guides.push(guide);
+
+                Some(self.guides.len() - 1)
+            }
+            Err(error) => {
+                self.import_error = Some(format!("JSON invalide : {error}"));
+
+                None
+            }
+        }
+    }
+
+    /// Removes an imported guide and loads the guides again to keep the indices aligned
+    ///
+    fn delete_imported_guide(&mut self, imported: usize) {
+        if imported < self.imported_guides.len() {
+            self.imported_guides.remove(imported);
+        }
+
+        self.guides.clear();
+        self.load_guides();
+    }
+
+    fn load_errors_ui(&mut self, ui: &mut Ui) {
+        if let Some(missing) = &self.missing_guide {
+            let mut dismissed = false;
+
+            ui.horizontal(|ui| {
+                ui.colored_label(
+                    ERROR_COLOR,
+                    format!(
+                        "Guide {missing} introuvable, {} sélectionné à la place",
+                        self.selected_guide
+                    ),
+                );
+                dismissed = ui.button("x").clicked();
+            });
+
+            if dismissed {
+                self.missing_guide = None;
+            }
+        }
+
+        if self.load_errors.is_empty() {
+            return;
+        }
+
+        let mut deleted = None;
+
+        CollapsingHeader::new(
+            RichText::new(format!(
+                "{} guides importés non chargés",
+                self.load_errors.len()
+            ))
+            .color(ERROR_COLOR),
+        )
+        .show(ui, |ui| {
+            for error in &self.load_errors {
+                ui.horizontal(|ui| {
+                    ui.label(&error.message);
+                    if ui.button("Supprimer").clicked() {
+                        deleted = Some(error.imported);
+                    }
+                });
+            }
+        });
+
+        if let Some(imported) = deleted {
+            self.delete_imported_guide(imported);
+        }
+    }
+
+    pub fn recipe_style(&self) -> Option<&BeerStyle> {
+        self.recipe_style.as_ref()
+    }
+
     pub fn show(&mut self, ui: &mut Ui) {
         egui::Frame::new().show(ui, |ui| {
+            if let Some(recipe_style) = &self.recipe_style {
+                let mut cleared = false;
+
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "Style de la recette : {}",
+                        recipe_style.reference()
+                    ));
+
+                    cleared = ui.button("x").clicked();
+                });
+
+                if cleared {
+                    self.recipe_style = None;
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+            }
+
+            let mut guide_index = self.guide_index;
+
+            ComboBox::from_label("Guide de styles")
+                .selected_text(
+                    self.guides
+                        .get(self.guide_index)
+                        .map(|guide| guide.reference.to_string())
+                        .unwrap_or_default(),
+                )
+                .show_ui(ui, |ui| {
+                    for (index, guide) in self.guides.iter().enumerate() {
+                        ui.selectable_value(&mut guide_index, index, guide.reference.to_string());
+                    }
+                });
+
+            let imported = self
+                .guides
+                .get(guide_index)
+                .and_then(|guide| guide.imported);
+
+            if let Some(imported) = imported {
+                if ui.button("Supprimer le guide importé").clicked() {
+                    if let Some(guide) = self.guides.first() {
+                        self.selected_guide = guide.reference.clone();
+                    }
+                    self.delete_imported_guide(imported);
+                    guide_index = self.guide_index;
+                }
+            }
+
+            self.load_errors_ui(ui);
+            guide_index = guide_index.min(self.guides.len().saturating_sub(1));
+
+            CollapsingHeader::new("Importer un guide").show(ui, |ui| {
+                if let Some(index) = self.import_guide_ui(ui) {
+                    guide_index = index;
+                }
+            });
+
+            let guide_changed = guide_index != self.guide_index;
+
+            if guide_changed {
+                self.guide_index = guide_index;
+                self.missing_guide = None;
+                if let Some(guide) = self.guides.get(guide_index) {
+                    self.selected_guide = guide.reference.clone();
+                }
+                self.query.category = None;
+                self.query.tags.clear();
+            }
+
+            let Some(guide) = self.guides.get_mut(self.guide_index) else {
+                ui.colored_label(ERROR_COLOR, "Aucun guide de styles chargé");
+                return;
+            };
+
+            ui.add_space(DEFAULT_SPACING);
+
+            CollapsingHeader::new("Styles suggérés pour la recette").show(ui, |ui| {
+                let mut picked = None;
+
+                for style_match in
+                    recommender::recommend_styles(&guide.beer_styles, &self.recipe_vitals)
+                        .iter()
+                        .take(RECOMMENDATIONS_COUNT)
+                {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button(&guide.beer_styles[style_match.index].name)
+                            .clicked()
+                        {
+                            picked = Some(style_match.index);
+                        }
+
+                        ui.label(format!("Écart : {:.2}", style_match.distance));
+
+                        if style_match.out_of_range.is_empty() {
+                            ui.colored_label(SUCCESS_COLOR, "Dans toutes les plages");
+                        } else {
+                            ui.colored_label(
+                                ERROR_COLOR,
+                                format!("Hors plage : {}", style_match.out_of_range.join(", ")),
+                            );
+                        }
+
+                        if !style_match.not_compared.is_empty() {
+                            ui.label(format!(
+                                "Non comparés : {}",
+                                style_match.not_compared.join(", ")
+                            ));
+                        }
+                    });
+                }
+
+                if let Some(index) = picked {
+                    self.recipe_style = Some(guide.beer_styles[index].clone());
+                }
+            });
+
+            ui.add_space(DEFAULT_SPACING);
+
+            if !guide.range_errors.is_empty() {
+                CollapsingHeader::new(
+                    RichText::new(format!(
+                        "{} plages invalides dans le guide",
+                        guide.range_errors.len()
+                    ))
+                    .color(ERROR_COLOR),
+                )
+                .show(ui, |ui| {
+                    for error in &guide.range_errors {
+                        ui.label(format!(
+                            "{} ({}) : {}",
+                            error.style, error.field, error.reason
+                        ));
+                    }
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+            }
+
             ui.label("Recherche par style (eng)");
 
-            if ui.text_edit_singleline(&mut self.prompt).changed() {
-                self.result = search_styles(&self.prompt, &self.beer_styles)
+            let mut changed = ui.text_edit_singleline(&mut self.query.text).changed();
+
+            CollapsingHeader::new("Filtres").show(ui, |ui| {
+                ComboBox::from_label("Catégorie")
+                    .selected_text(self.query.category.as_deref().unwrap_or("Toutes"))
+                    .show_ui(ui, |ui| {
+                        changed |= ui
+                            .selectable_value(&mut self.query.category, None, "Toutes")
+                            .changed();
+
+                        for category in style_categories(&guide.beer_styles) {
+                            changed |= ui
+                                .selectable_value(
+                                    &mut self.query.category,
+                                    Some(category.clone()),
+                                    &category,
+                                )
+                                .changed();
+                        }
+                    });
+
+                changed |= self.query.og.show(ui, "OG (SG)", 0.001);
+                changed |= self.query.abv.show(ui, "ABV (%)", 0.1);
+                changed |= self.query.ibu.show(ui, "IBU", 1.0);
+                changed |= self.query.srm.show(ui, "SRM", 0.5);
+            });
+
+            CollapsingHeader::new("Tags").show(ui, |ui| {
+                let styles = if self.query.is_active() {
+                    &self.result
+                } else {
+                    &guide.beer_styles
+                };
+
+                ui.horizontal_wrapped(|ui| {
+                    for (tag, count) in tags::count_tags(styles) {
+                        let selected = self.query.tags.contains(tag);
+
+                        if ui
+                            .selectable_label(selected, format!("{tag} ({count})"))
+                            .clicked()
+                        {
+                            if selected {
+                                self.query.tags.remove(tag);
+                            } else {
+                                self.query.tags.insert(tag.to_string());
+                            }
+
+                            changed = true;
+                        }
+                    }
+                });
+
+                if !self.query.tags.is_empty() && ui.button("Effacer les tags").clicked() {
+                    self.query.tags.clear();
+                    changed = true;
+                }
+            });
+
+            if changed || guide_changed {
+                self.result = search::search_styles(&self.query, &guide.beer_styles)
             };
 
             ui.add_space(DEFAULT_SPACING);
 
             ScrollArea::vertical().show(ui, |ui| {
-                if !self.prompt.is_empty() {
+                if self.query.is_active() {
                     for style in &mut self.result {
-                        style_ui(style, ui.ctx());
+                        if style_ui(style, ui.ctx()) {
+                            self.recipe_style = Some(style.clone());
+                        }
                     }
 
                     for style in self.result.iter_mut() {
@@ -98,11 +526,13 @@ impl BJCPStyleIndex {
                         }
                     }
                 } else {
-                    for style in &mut self.beer_styles {
-                        style_ui(style, ui.ctx());
+                    for style in &mut guide.beer_styles {
+                        if style_ui(style, ui.ctx()) {
+                            self.recipe_style = Some(style.clone());
+                        }
                     }
 
-                    for style in self.beer_styles.iter_mut() {
+                    for style in guide.beer_styles.iter_mut() {
                         if ui.button(&style.name).clicked() {
                             style.opened = !style.opened;
                         }
@@ -113,20 +543,29 @@ impl BJCPStyleIndex {
     }
 }
 
-fn search_styles(prompt: &str, styles: &[BeerStyle]) -> Vec<BeerStyle> {
+fn style_categories(styles: &[BeerStyle]) -> BTreeSet<String> {
     styles
         .iter()
-        .filter(|&style| style.name.to_lowercase().contains(&prompt.to_lowercase()))
-        .cloned()
+        .filter_map(|style| style.category.clone())
         .collect()
 }
 
-fn style_ui(style: &mut BeerStyle, ctx: &Context) {
+/// Returns true when the style is picked for the recipe
+///
+fn style_ui(style: &mut BeerStyle, ctx: &Context) -> bool {
+    let mut picked = false;
+
     Window::new(&style.name)
         .default_size([400., 400.])
         .open(&mut style.opened)
         .show(ctx, |ui| {
             ScrollArea::vertical().id_salt(&style.name).show(ui, |ui| {
+                if ui.button("Choisir pour la recette").clicked() {
+                    picked = true;
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
                 if let Some(number) = &style.number {
                     ui.label(format!("BJCP Number: {number}"));
                 };
@@ -139,99 +578,45 @@ fn style_ui(style: &mut BeerStyle, ctx: &Context) {
 
                 ui.add_space(DEFAULT_SPACING);
 
-                ui.horizontal(|ui| {
-                    ui.label("IBU");
-
-                    if let Some(ibu_min) = &style.ibumin {
-                        ui.label(ibu_min);
-                    };
-
-                    ui.label("-");
-
-                    if let Some(ibu_max) = &style.ibumax {
-                        ui.label(ibu_max);
-                    };
-                });
-
-                ui.horizontal(|ui| {
-                    ui.label("Original gravity");
-
-                    if let Some(og_min) = &style.ogmin {
-                        ui.label(og_min);
-
-                        if let Ok(og_min) = og_min.parse::<f32>() {
-                            ui.label(format!("({:.1} °P)", convert_sg_to_plato(og_min)));
-                        }
-                    };
-
-                    ui.label("-");
-
-                    if let Some(og_max) = &style.ogmax {
-                        ui.label(og_max);
-
-                        if let Ok(og_max) = og_max.parse::<f32>() {
-                            ui.label(format!("({:.1} °P)", convert_sg_to_plato(og_max)));
-                        }
-                    };
-                });
-
-                ui.horizontal(|ui| {
-                    ui.label("Final gravity");
-
-                    if let Some(fg_min) = &style.fgmin {
-                        ui.label(fg_min);
-
-                        if let Ok(fg_min) = fg_min.parse::<f32>() {
-                            ui.label(format!("({:.1} °P)", convert_sg_to_plato(fg_min)));
-                        }
-                    };
-
-                    ui.label("-");
-
-                    if let Some(fg_max) = &style.fgmax {
-                        ui.label(fg_max);
-
-                        if let Ok(fg_max) = fg_max.parse::<f32>() {
-                            ui.label(format!("({:.1} °P)", convert_sg_to_plato(fg_max)));
-                        }
-                    };
-                });
-
-                ui.horizontal(|ui| {
-                    ui.label("ABV");
-
-                    if let Some(abv_min) = &style.abvmin {
-                        ui.label(abv_min);
-                    };
-
-                    ui.label("-");
-
-                    if let Some(abv_max) = &style.abvmax {
-                        ui.label(abv_max);
-                    };
-                });
-
-                ui.horizontal(|ui| {
-                    ui.label("SRM");
+                let ranges = style.ranges;
 
-                    if let Some(srm_min) = &style.srmmin {
-                        ui.label(srm_min);
+                if let Some(ibu) = ranges.ibu {
+                    ui.label(format!("IBU {:.0} - {:.0}", ibu.min, ibu.max));
+                };
 
-                        if let Ok(srm_min) = srm_min.parse::<f32>() {
-                            ui.label(format!("({:.1} EBC)", convert_srm_to_ebc(srm_min)));
-                        }
-                    };
+                if let Some(og) = ranges.og {
+                    ui.label(format!(
+                        "Original gravity {:.3} - {:.3} ({:.1} °P - {:.1} °P)",
+                        og.min,
+                        og.max,
+                        convert_sg_to_plato(og.min),
+                        convert_sg_to_plato(og.max)
+                    ));
+                };
 
-                    ui.label("-");
+                if let Some(fg) = ranges.fg {
+                    ui.label(format!(
+                        "Final gravity {:.3} - {:.3} ({:.1} °P - {:.1} °P)",
+                        fg.min,
+                        fg.max,
+                        convert_sg_to_plato(fg.min),
+                        convert_sg_to_plato(fg.max)
+                    ));
+                };
 
-                    if let Some(srm_max) = &style.srmmax {
-                        ui.label(srm_max);
+                if let Some(abv) = ranges.abv {
+                    ui.label(format!("ABV {:.1} - {:.1}", abv.min, abv.max));
+                };
 
-                        if let Ok(srm_max) = srm_max.parse::<f32>() {
-                            ui.label(format!("({:.1} EBC)", convert_srm_to_ebc(srm_max)));
-                        }
-                    };
-                });
+                if let Some(srm) = ranges.srm {
+                    ui.label(format!(
+                        "SRM {:.1} - {:.1} ({:.1} EBC - {:.1} EBC)",
+                        srm.min,
+                        srm.max,
+                        convert_srm_to_ebc(srm.min),
+                        convert_srm_to_ebc(srm.max)
+                    ));
+                };
 
                 ui.add_space(DEFAULT_SPACING);
 
@@ -297,4 +682,6 @@ fn style_ui(style: &mut BeerStyle, ctx: &Context) {
                 };
             });
         });
+
+    picked
 }
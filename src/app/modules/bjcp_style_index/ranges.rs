@@ -0,0 +1,97 @@
+use super::BeerStyle;
+
+/// Inclusive range of a style parameter
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StyleRange {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl StyleRange {
+    pub fn contains(&self, value: f32) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+
+    pub fn bounds(self) -> (f32, f32) {
+        (self.min, self.max)
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max - self.min
+    }
+
+    pub fn map(&self, convert: impl Fn(f32) -> f32) -> Self {
+        Self {
+            min: convert(self.min),
+            max: convert(self.max),
+        }
+    }
+}
+
+/// Style ranges parsed once at load, gravities in SG and color in SRM
+/// A range is None when the style guide doesn't define it or when it failed to parse
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleRanges {
+    pub og: Option<StyleRange>,
+    pub fg: Option<StyleRange>,
+    pub abv: Option<StyleRange>,
+    pub ibu: Option<StyleRange>,
+    pub srm: Option<StyleRange>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RangeError {
+    pub style: String,
+    pub field: &'static str,
+    pub reason: String,
+}
+
+impl StyleRanges {
+    pub fn parse(style: &BeerStyle) -> (Self, Vec<RangeError>) {
+        let mut errors = vec![];
+
+        let mut parse = |field, min: &Option<String>, max: &Option<String>| {
+            parse_range(min, max).unwrap_or_else(|reason| {
+                errors.push(RangeError {
+                    style: style.name.clone(),
+                    field,
+                    reason,
+                });
+
+                None
+            })
+        };
+
+        let ranges = Self {
+            og: parse("OG", &style.ogmin, &style.ogmax),
+            fg: parse("FG", &style.fgmin, &style.fgmax),
+            abv: parse("ABV", &style.abvmin, &style.abvmax),
+            ibu: parse("IBU", &style.ibumin, &style.ibumax),
+            srm: parse("SRM", &style.srmmin, &style.srmmax),
+        };
+
+        (ranges, errors)
+    }
+}
+
+fn parse_range(min: &Option<String>, max: &Option<String>) -> Result<Option<StyleRange>, String> {
+    let (min, max) = match (min, max) {
+        (None, None) => return Ok(None),
+        (Some(min), Some(max)) => (parse_bound(min)?, parse_bound(max)?),
+        (Some(_), None) => return Err("max is missing".into()),
+        (None, Some(_)) => return Err("min is missing".into()),
+    };
+
+    if min > max {
+        return Err(format!("min ({min}) is greater than max ({max})"));
+    }
+
+    Ok(Some(StyleRange { min, max }))
+}
+
+fn parse_bound(bound: &str) -> Result<f32, String> {
+    match bound.trim().parse::<f32>() {
+        Ok(bound) if bound.is_finite() => Ok(bound),
+        _ => Err(format!("`{bound}` is not a number")),
+    }
+}
@@ -0,0 +1,77 @@
+use super::{BeerStyle, RecipeVitals, StyleRange};
+
+pub struct StyleMatch {
+    pub index: usize,
+    pub distance: f32,
+    pub out_of_range: Vec<&'static str>,
+    /// Parameters the style has no range for
+    pub not_compared: Vec<&'static str>,
+}
+
+/// Ranks styles by how close their ranges are to the recipe vitals
+/// A parameter inside its range scores 0, outside it scores its gap
+/// expressed in multiples of the range width
+/// The distance is averaged over the compared parameters so styles defining
+/// fewer ranges don't rank higher, styles without any range (specialty beers) are left out
+///
+pub fn recommend_styles(styles: &[BeerStyle], vitals: &RecipeVitals) -> Vec<StyleMatch> {
+    let mut matches: Vec<StyleMatch> = styles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, style)| {
+            let ranges = style.ranges();
+
+            let parameters = [
+                ("OG", ranges.og, vitals.og),
+                ("FG", ranges.fg, vitals.fg),
+                ("ABV", ranges.abv, vitals.abv),
+                ("IBU", ranges.ibu, vitals.ibu),
+                ("SRM", ranges.srm, vitals.srm),
+            ];
+
+            let mut compared = 0;
+            let mut squared_distance = 0.0;
+            let mut out_of_range = vec![];
+            let mut not_compared = vec![];
+
+            for (label, range, value) in parameters {
+                let Some(range) = range else {
+                    not_compared.push(label);
+                    continue;
+                };
+
+                compared += 1;
+
+                let gap = range_gap(range, value);
+
+                if gap > 0.0 {
+                    out_of_range.push(label);
+                    squared_distance += gap.powf(2.0);
+                }
+            }
+
+            (compared > 0).then(|| StyleMatch {
+                index,
+                distance: (squared_distance / compared as f32).sqrt(),
+                out_of_range,
+                not_compared,
+            })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    matches
+}
+
+fn range_gap(range: StyleRange, value: f32) -> f32 {
+    let width = range.width().max(f32::EPSILON);
+
+    if value < range.min {
+        (range.min - value) / width
+    } else if value > range.max {
+        (value - range.max) / width
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,116 @@
+use super::{BeerStyle, StyleRange};
+use crate::app::modules::range_filter::RangeFilter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Gravities are expressed in SG and color in SRM, like in the style guide
+#[derive(Deserialize, Serialize, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct StyleQuery {
+    pub text: String,
+    pub category: Option<String>,
+    pub og: RangeFilter,
+    pub abv: RangeFilter,
+    pub ibu: RangeFilter,
+    pub srm: RangeFilter,
+    pub tags: BTreeSet<String>,
+}
+
+impl StyleQuery {
+    pub fn is_active(&self) -> bool {
+        !self.text.trim().is_empty()
+            || self.category.is_some()
+            || self.og.is_active()
+            || self.abv.is_active()
+            || self.ibu.is_active()
+            || self.srm.is_active()
+            || !self.tags.is_empty()
+    }
+}
+
+/// Every word of the query has to be found in at least one text field
+/// Results are ranked by the weight of the fields the words were found in
+///
+pub fn search_styles(query: &StyleQuery, styles: &[BeerStyle]) -> Vec<BeerStyle> {
+    let terms: Vec<String> = query
+        .text
+        .to_lowercase()
+        .split_whitespace()
+        .map(String::from)
+        .collect();
+
+    let mut results: Vec<(u32, &BeerStyle)> = styles
+        .iter()
+        .filter(|style| {
+            query
+                .category
+                .as_ref()
+                .is_none_or(|category| style.category.as_ref() == Some(category))
+        })
+        .filter(|style| query.tags.is_subset(style.tags()))
+        .filter(|style| {
+            let ranges = style.ranges();
+
+            query.og.overlaps(ranges.og.map(StyleRange::bounds))
+                && query.abv.overlaps(ranges.abv.map(StyleRange::bounds))
+                && query.ibu.overlaps(ranges.ibu.map(StyleRange::bounds))
+                && query.srm.overlaps(ranges.srm.map(StyleRange::bounds))
+        })
+        .filter_map(|style| relevance(style, &terms).map(|score| (score, style)))
+        .collect();
+
+    results.sort_by(|(score_a, style_a), (score_b, style_b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| style_a.name.cmp(&style_b.name))
+    });
+
+    results
+        .into_iter()
+        .map(|(_, style)| style.clone())
+        .collect()
+}
+
+/// Returns None when a term matches no field
+///
+fn relevance(style: &BeerStyle, terms: &[String]) -> Option<u32> {
+    let fields = [
+        (Some(&style.name), 10),
+        (style.tags.as_ref(), 6),
+        (style.category.as_ref(), 5),
+        (style.number.as_ref(), 5),
+        (style.characteristicingredients.as_ref(), 3),
+        (style.commercialexamples.as_ref(), 3),
+        (style.overallimpression.as_ref(), 2),
+        (style.aroma.as_ref(), 2),
+        (style.flavor.as_ref(), 2),
+        (style.appearance.as_ref(), 1),
+        (style.mouthfeel.as_ref(), 1),
+        (style.comments.as_ref(), 1),
+        (style.history.as_ref(), 1),
+        (style.stylecomparison.as_ref(), 1),
+    ];
+
+    let fields: Vec<(String, u32)> = fields
+        .into_iter()
+        .filter_map(|(field, weight)| field.map(|field| (field.to_lowercase(), weight)))
+        .collect();
+
+    let mut score = 0;
+
+    for term in terms {
+        let term_score: u32 = fields
+            .iter()
+            .filter(|(field, _)| field.contains(term.as_str()))
+            .map(|(_, weight)| weight)
+            .sum();
+
+        if term_score == 0 {
+            return None;
+        }
+
+        score += term_score;
+    }
+
+    Some(score)
+}
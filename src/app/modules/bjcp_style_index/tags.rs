@@ -0,0 +1,52 @@
+use super::BeerStyle;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Splits comma-separated tags, lowercased with inner spaces turned into hyphens
+///
+pub fn parse_tags(tags: &Option<String>) -> BTreeSet<String> {
+    tags.iter()
+        .flat_map(|tags| tags.split(','))
+        .map(|tag| {
+            tag.split_whitespace()
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_lowercase()
+        })
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Merges spellings that only differ by hyphens (`northamerica`, `north-america`)
+/// into the most hyphenated one found in the guide
+///
+pub fn unify_tags(styles: &mut [BeerStyle]) {
+    let mut spellings: HashMap<String, String> = HashMap::new();
+
+    for tag in styles.iter().flat_map(|style| style.tag_set.iter()) {
+        let spelling = spellings.entry(tag.replace('-', "")).or_insert(tag.clone());
+
+        if tag.matches('-').count() > spelling.matches('-').count() {
+            *spelling = tag.clone();
+        }
+    }
+
+    for style in styles {
+        style.tag_set = style
+            .tag_set
+            .iter()
+            .map(|tag| spellings[&tag.replace('-', "")].clone())
+            .collect();
+    }
+}
+
+/// Number of styles carrying each tag
+///
+pub fn count_tags(styles: &[BeerStyle]) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+
+    for tag in styles.iter().flat_map(|style| style.tag_set.iter()) {
+        *counts.entry(tag.as_str()).or_insert(0) += 1;
+    }
+
+    counts
+}
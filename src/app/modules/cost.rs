@@ -0,0 +1,319 @@
+use crate::app::modules::ingredients_index::{self, IngredientKind, IngredientUse};
+use crate::app::modules::ui_defaults::*;
+use eframe::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum PriceUnit {
+    #[default]
+    PerKg,
+    Per100g,
+    /// Pack of `Price::pack_size` g
+    PerPack,
+}
+
+impl PriceUnit {
+    const ALL: [PriceUnit; 3] = [Self::PerKg, Self::Per100g, Self::PerPack];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::PerKg => "€/kg",
+            Self::Per100g => "€/100 g",
+            Self::PerPack => "€/paquet",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct Price {
+    pub kind: IngredientKind,
+    pub name: String,
+    pub amount: f32,
+    pub unit: PriceUnit,
+    /// g
+    pub pack_size: f32,
+}
+
+impl Price {
+    /// Cost of the weight used (g), a pack is charged pro rata
+    ///
+    pub fn cost_of(&self, weight: f32) -> f32 {
+        match self.unit {
+            PriceUnit::PerKg => self.amount * weight / 1000.,
+            PriceUnit::Per100g => self.amount * weight / 100.,
+            PriceUnit::PerPack if self.pack_size > 0. => self.amount * weight / self.pack_size,
+            PriceUnit::PerPack => 0.,
+        }
+    }
+
+    fn matches(&self, kind: IngredientKind, name: &str) -> bool {
+        ingredients_index::same_ingredient(self.kind, &self.name, kind, name)
+    }
+}
+
+/// Optional costs besides the ingredients, None when not taken into account
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ExtraCosts {
+    /// €/m³
+    pub water_price: Option<f32>,
+    /// kWh used for the batch
+    pub energy_used: f32,
+    /// €/kWh
+    pub energy_price: Option<f32>,
+    /// kg used for the batch
+    pub co2_used: f32,
+    /// €/kg
+    pub co2_price: Option<f32>,
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct Cost {
+    /// Beer left once fermented and packaged, in L
+    #[serde(skip)]
+    pub packaged_volume: f32,
+    /// L
+    pub water_volume: f32,
+    #[serde(skip)]
+    pub recipe_uses: Vec<IngredientUse>,
+    prices: Vec<Price>,
+    extras: ExtraCosts,
+    /// L
+    unit_volume: f32,
+}
+
+impl Cost {
+    /// Cost by category: malts, hops, yeasts, water, energy and CO2
+    ///
+    fn breakdown(&self) -> [(&'static str, f32); 6] {
+        let (mut malts, mut hops, mut yeasts) = (0., 0., 0.);
+
+        for ingredient in &self.recipe_uses {
+            if let Some(price) = self
+                .prices
+                .iter()
+                .find(|price| price.matches(ingredient.kind, &ingredient.name))
+            {
+                let category = match ingredient.kind {
+                    IngredientKind::Malt => &mut malts,
+                    IngredientKind::Hop => &mut hops,
+                    IngredientKind::Yeast => &mut yeasts,
+                };
+
+                *category += price.cost_of(ingredient.weight);
+            }
+        }
+
+        let extras = &self.extras;
+
+        [
+            ("Malts", malts),
+            ("Houblons", hops),
+            ("Ferments", yeasts),
+            (
+                "Eau",
+                extras.water_price.unwrap_or_default() * self.water_volume / 1000.,
+            ),
+            (
+                "Énergie",
+                extras.energy_price.unwrap_or_default() * extras.energy_used,
+            ),
+            (
+                "CO2",
+                extras.co2_price.unwrap_or_default() * extras.co2_used,
+            ),
+        ]
+    }
+}
+
+impl super::AppModule for Cost {
+    fn new() -> Self {
+        Self {
+            unit_volume: 0.33,
+            ..Default::default()
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        ui.heading("Coût");
+        ui.add_space(DEFAULT_SPACING);
+
+        egui::Frame::new()
+            .fill(LIGHTER_COLOR)
+            .inner_margin(DEFAULT_PADDING)
+            .corner_radius(DEFAULT_CORNER_RADIUS)
+            .show(ui, |ui| {
+                let uses = std::mem::take(&mut self.recipe_uses);
+
+                Grid::new("cout-ingredients").striped(true).show(ui, |ui| {
+                    ui.label("Ingrédient");
+                    ui.label("Quantité (g)");
+                    ui.label("Prix");
+                    ui.label("");
+                    ui.label("Coût (€)");
+                    ui.end_row();
+
+                    for (row, ingredient) in uses
+                        .iter()
+                        .filter(|ingredient| !ingredient.name.trim().is_empty())
+                        .enumerate()
+                    {
+                        let existing = self
+                            .prices
+                            .iter()
+                            .position(|price| price.matches(ingredient.kind, &ingredient.name));
+                        let mut price = match existing {
+                            Some(index) => self.prices[index].clone(),
+                            None => Price {
+                                kind: ingredient.kind,
+                                name: ingredient.name.trim().to_string(),
+                                ..Default::default()
+                            },
+                        };
+
+                        ui.label(format!("{} - {}", ingredient.kind.label(), ingredient.name));
+                        ui.label(format!("{:.1}", ingredient.weight));
+
+                        let mut changed = ui
+                            .add(
+                                DragValue::new(&mut price.amount)
+                                    .speed(0.05)
+                                    .range(0.0..=f32::MAX),
+                            )
+                            .changed();
+                        ui.horizontal(|ui| {
+                            ComboBox::from_id_salt(("cout-unite", row))
+                                .selected_text(price.unit.label())
+                                .show_ui(ui, |ui| {
+                                    for unit in PriceUnit::ALL {
+                                        changed |= ui
+                                            .selectable_value(&mut price.unit, unit, unit.label())
+                                            .changed();
+                                    }
+                                });
+                            if price.unit == PriceUnit::PerPack {
+                                ui.label("de (g)");
+                                changed |= ui
+                                    .add(
+                                        DragValue::new(&mut price.pack_size)
+                                            .speed(0.5)
+                                            .range(0.0..=f32::MAX),
+                                    )
+                                    .changed();
+                            }
+                        });
+                        ui.label(format!("{:.2}", price.cost_of(ingredient.weight)));
+
+                        // Prices are only kept once set, not for every name typed in the recipe
+                        if changed {
+                            match existing {
+                                Some(index) => self.prices[index] = price,
+                                None => self.prices.push(price),
+                            }
+                        }
+
+                        ui.end_row();
+                    }
+                });
+
+                self.recipe_uses = uses;
+
+                ui.add_space(DEFAULT_SPACING);
+
+                CollapsingHeader::new("Autres coûts").show(ui, |ui| {
+                    let extras = &mut self.extras;
+
+                    optional_price_ui(ui, "Eau (€/m³)", &mut extras.water_price);
+                    ui.label(format!("Volume d'eau (L): {:.1}", self.water_volume));
+
+                    ui.add_space(DEFAULT_SPACING);
+
+                    optional_price_ui(ui, "Énergie (€/kWh)", &mut extras.energy_price);
+                    ui.horizontal(|ui| {
+                        ui.label("Consommation (kWh): ");
+                        ui.add(
+                            DragValue::new(&mut extras.energy_used)
+                                .speed(0.1)
+                                .range(0.0..=f32::MAX),
+                        );
+                    });
+
+                    ui.add_space(DEFAULT_SPACING);
+
+                    optional_price_ui(ui, "CO2 (€/kg)", &mut extras.co2_price);
+                    ui.horizontal(|ui| {
+                        ui.label("Consommation (kg): ");
+                        ui.add(
+                            DragValue::new(&mut extras.co2_used)
+                                .speed(0.01)
+                                .range(0.0..=f32::MAX),
+                        );
+                    });
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Contenance d'une unité (L): ");
+                    ui.add(
+                        DragValue::new(&mut self.unit_volume)
+                            .speed(0.01)
+                            .range(0.0..=f32::MAX),
+                    );
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                let breakdown = self.breakdown();
+                let total: f32 = breakdown.iter().map(|(_, cost)| cost).sum();
+
+                Grid::new("cout-repartition").show(ui, |ui| {
+                    for (category, cost) in breakdown {
+                        ui.label(category);
+                        ui.label(format!("{cost:.2} €"));
+                        if total > 0. {
+                            ui.label(format!("{:.0} %", cost / total * 100.));
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.label(format!("Coût total du brassin : {total:.2} €"));
+
+                if self.packaged_volume > 0. {
+                    let per_liter = total / self.packaged_volume;
+
+                    ui.label(format!("Coût par litre : {per_liter:.2} €"));
+                    ui.label(format!(
+                        "Coût par unité : {:.2} € ({:.0} unités)",
+                        per_liter * self.unit_volume,
+                        if self.unit_volume > 0. {
+                            (self.packaged_volume / self.unit_volume).floor()
+                        } else {
+                            0.
+                        }
+                    ));
+                }
+            });
+    }
+}
+
+fn optional_price_ui(ui: &mut Ui, label: &str, price: &mut Option<f32>) {
+    let mut enabled = price.is_some();
+    let mut value = price.unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut enabled, label);
+        ui.add_enabled(
+            enabled,
+            DragValue::new(&mut value).speed(0.01).range(0.0..=f32::MAX),
+        );
+    });
+
+    *price = enabled.then_some(value);
+}
@@ -0,0 +1,225 @@
+use crate::app::modules::ui_defaults::*;
+use eframe::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+/// Brewing system losses, volumes in L
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct Equipment {
+    pub name: String,
+    pub mash_tun_dead_space: f32,
+    /// L/kg
+    pub grain_absorption: f32,
+    pub kettle_dead_space: f32,
+    pub trub_chiller_loss: f32,
+    pub fermenter_loss: f32,
+    /// L/h
+    pub boil_off_rate: f32,
+    /// Lauter efficiency, in %
+    pub efficiency: u8,
+}
+
+impl Default for Equipment {
+    fn default() -> Self {
+        Self {
+            name: "Équipement par défaut".to_string(),
+            mash_tun_dead_space: 0.,
+            grain_absorption: 0.8,
+            kettle_dead_space: 0.,
+            trub_chiller_loss: 0.,
+            fermenter_loss: 0.,
+            boil_off_rate: 3.,
+            efficiency: 75,
+        }
+    }
+}
+
+impl Equipment {
+    fn edit_ui(&mut self, ui: &mut Ui) -> bool {
+        let mut efficiency_changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Nom : ");
+            ui.text_edit_singleline(&mut self.name);
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        for (label, value, range) in [
+            (
+                "Volume mort de la cuve d'empâtage (L): ",
+                &mut self.mash_tun_dead_space,
+                0.0..=50.0,
+            ),
+            (
+                "Absorption du grain (L/kg): ",
+                &mut self.grain_absorption,
+                0.0..=2.0,
+            ),
+            (
+                "Volume mort de la cuve d'ébullition (L): ",
+                &mut self.kettle_dead_space,
+                0.0..=50.0,
+            ),
+            (
+                "Pertes trub et refroidisseur (L): ",
+                &mut self.trub_chiller_loss,
+                0.0..=50.0,
+            ),
+            (
+                "Pertes dans le fermenteur (L): ",
+                &mut self.fermenter_loss,
+                0.0..=50.0,
+            ),
+            ("Évaporation (L/h): ", &mut self.boil_off_rate, 0.0..=50.0),
+        ] {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                ui.add(Slider::new(value, range));
+            });
+
+            ui.add_space(DEFAULT_SPACING);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Efficacité de filtration par défaut (%): ");
+            efficiency_changed = ui.add(Slider::new(&mut self.efficiency, 0..=100)).changed();
+        });
+
+        efficiency_changed
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EquipmentProfiles {
+    profiles: Vec<Equipment>,
+    /// Index of the profile used by the recipe
+    selected: usize,
+    /// Name of the profile in older saves, migrated to `selected`
+    #[serde(skip_serializing)]
+    recipe_equipment: Option<String>,
+    #[serde(skip)]
+    efficiency_changed: bool,
+}
+
+impl Default for EquipmentProfiles {
+    fn default() -> Self {
+        Self {
+            profiles: vec![Equipment::default()],
+            selected: 0,
+            recipe_equipment: None,
+            efficiency_changed: false,
+        }
+    }
+}
+
+impl EquipmentProfiles {
+    fn selected_index(&self) -> usize {
+        if self.selected < self.profiles.len() {
+            self.selected
+        } else {
+            0
+        }
+    }
+
+    /// Name not used by any profile yet
+    ///
+    fn unique_name(&self, name: &str) -> String {
+        let taken = |name: &str| self.profiles.iter().any(|profile| profile.name == name);
+        let mut candidate = format!("{name} (copie)");
+        let mut count = 2;
+
+        while taken(&candidate) {
+            candidate = format!("{name} (copie {count})");
+            count += 1;
+        }
+
+        candidate
+    }
+
+    /// Profile referenced by the recipe, the first one when the index is unknown
+    ///
+    pub fn equipment(&self) -> Equipment {
+        self.profiles
+            .get(self.selected_index())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Default efficiency of the profile, once after it was picked or edited
+    ///
+    pub fn take_efficiency_changed(&mut self) -> Option<u8> {
+        std::mem::take(&mut self.efficiency_changed).then(|| self.equipment().efficiency)
+    }
+}
+
+impl super::AppModule for EquipmentProfiles {
+    fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if self.profiles.is_empty() {
+            self.profiles.push(Equipment::default());
+        }
+
+        if let Some(name) = self.recipe_equipment.take() {
+            self.selected = self
+                .profiles
+                .iter()
+                .position(|profile| profile.name == name)
+                .unwrap_or_default();
+        }
+
+        let selected = self.selected_index();
+
+        ui.horizontal(|ui| {
+            ui.heading("Équipement");
+
+            ComboBox::from_id_salt("equipement")
+                .selected_text(&self.profiles[selected].name)
+                .show_ui(ui, |ui| {
+                    for (index, profile) in self.profiles.iter().enumerate() {
+                        if ui
+                            .selectable_label(index == selected, &profile.name)
+                            .clicked()
+                        {
+                            self.selected = index;
+                            self.efficiency_changed = true;
+                        }
+                    }
+                });
+
+            if ui.button("Dupliquer").clicked() {
+                let mut copy = self.profiles[selected].clone();
+                copy.name = self.unique_name(&copy.name);
+                self.profiles.push(copy);
+                self.selected = self.profiles.len() - 1;
+            }
+
+            if self.profiles.len() > 1 && ui.button("Supprimer").clicked() {
+                self.profiles.remove(selected);
+                self.selected = 0;
+                self.efficiency_changed = true;
+            }
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let selected = self.selected_index();
+
+        CollapsingHeader::new("Profil d'équipement").show(ui, |ui| {
+            egui::Frame::new()
+                .fill(LIGHTER_COLOR)
+                .inner_margin(DEFAULT_PADDING)
+                .corner_radius(DEFAULT_CORNER_RADIUS)
+                .show(ui, |ui| {
+                    self.efficiency_changed |= self.profiles[selected].edit_ui(ui);
+                });
+        });
+    }
+}
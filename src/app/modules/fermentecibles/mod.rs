@@ -1,12 +1,47 @@
+use crate::app::modules::ingredients_index::{IngredientKind, IngredientUse};
 use crate::app::modules::math;
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
 use serde::{Deserialize, Serialize};
 
+/// Drives the malt acidity in the mash pH estimate
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum MaltType {
+    #[default]
+    Base,
+    Crystal,
+    Roast,
+}
+
+impl MaltType {
+    const ALL: [MaltType; 3] = [Self::Base, Self::Crystal, Self::Roast];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Base => "Malt de base",
+            Self::Crystal => "Caramel / crystal",
+            Self::Roast => "Torréfié",
+        }
+    }
+}
+
+/// What the mash sees of a fermentecible, weight in g
+pub struct MashMalt {
+    pub name: String,
+    pub malt_type: MaltType,
+    pub ebc: u8,
+    pub weight: f32,
+}
+
 #[derive(Deserialize, Serialize, Default)]
 struct Fermentecible {
     name: String,
+    #[serde(default)]
+    malt_type: MaltType,
+    /// Sugars and extracts skip the mash
+    #[serde(default)]
+    added_to_boil: bool,
     extract: f32,
     humidity: f32,
     ebc: u8,
@@ -18,17 +53,77 @@ struct Fermentecible {
 #[derive(Deserialize, Serialize, Default)]
 pub struct Fermentecibles {
     pub total_weight: f32,
+    /// Weight of the fermentecibles going through the mash, in g
+    #[serde(default)]
+    pub grain_weight: f32,
     pub original_gravity: f32,
-    pub efficiency: u8,
+    /// %
+    #[serde(skip)]
+    pub conversion_efficiency: u8,
+    /// %
+    #[serde(skip)]
+    pub lauter_efficiency: u8,
+    /// Share of the kettle wort reaching the fermenter, in %
+    #[serde(skip)]
+    pub kettle_efficiency: f32,
     pub batch_size: u16,
     pub ebc: u8,
     fermentecibles: Vec<Fermentecible>,
 }
 
+impl Fermentecibles {
+    pub fn ingredient_uses(&self) -> Vec<IngredientUse> {
+        self.fermentecibles
+            .iter()
+            .map(|fermentecible| IngredientUse {
+                kind: IngredientKind::Malt,
+                name: fermentecible.name.clone(),
+                weight: fermentecible.weight,
+            })
+            .collect()
+    }
+
+    /// Extract in g the mashed fermentecibles would give at 100 % efficiency
+    ///
+    pub fn mash_potential_extract(&self) -> f32 {
+        self.potential_extract(false)
+    }
+
+    /// Extract in g of the fermentecibles added to the boil
+    ///
+    pub fn boil_potential_extract(&self) -> f32 {
+        self.potential_extract(true)
+    }
+
+    fn potential_extract(&self, added_to_boil: bool) -> f32 {
+        self.fermentecibles
+            .iter()
+            .filter(|fermentecible| fermentecible.added_to_boil == added_to_boil)
+            .map(|fermentecible| {
+                fermentecible.weight
+                    * (fermentecible.extract / 100.0)
+                    * (1.0 - fermentecible.humidity / 100.0)
+            })
+            .sum()
+    }
+
+    pub fn mash_malts(&self) -> Vec<MashMalt> {
+        self.fermentecibles
+            .iter()
+            .filter(|fermentecible| !fermentecible.added_to_boil)
+            .map(|fermentecible| MashMalt {
+                name: fermentecible.name.clone(),
+                malt_type: fermentecible.malt_type,
+                ebc: fermentecible.ebc,
+                weight: fermentecible.weight,
+            })
+            .collect()
+    }
+}
+
 impl super::AppModule for Fermentecibles {
     fn new() -> Self {
         Self {
-            efficiency: 80,
             ..Default::default()
         }
     }
@@ -55,13 +150,24 @@ impl super::AppModule for Fermentecibles {
         let mut mcus = vec![];
         let mut ratios = vec![];
 
+        let mash_efficiency =
+            math::compute_mash_efficiency(self.conversion_efficiency, self.lauter_efficiency);
+        let brewhouse_efficiency =
+            math::compute_brewhouse_efficiency(mash_efficiency, self.kettle_efficiency);
+
+        self.grain_weight = 0.;
+
         for fermentecible in &mut self.fermentecibles {
             let total_extract: f32 = math::compute_total_extract(self.original_gravity);
 
             let fermentecible_extractable = math::compute_per_malt_extractable(
                 total_extract,
                 fermentecible.ratio,
-                self.efficiency,
+                if fermentecible.added_to_boil {
+                    self.kettle_efficiency
+                } else {
+                    brewhouse_efficiency
+                },
             );
 
             fermentecible.weight = math::compute_grain_bill(
@@ -74,6 +180,10 @@ impl super::AppModule for Fermentecibles {
             fermentecible.mcu =
                 math::compute_mcu(fermentecible.ebc, fermentecible.weight, self.batch_size);
 
+            if !fermentecible.added_to_boil {
+                self.grain_weight += fermentecible.weight;
+            }
+
             ratios.push(fermentecible.ratio);
             weights.push(fermentecible.weight);
             mcus.push(fermentecible.mcu);
@@ -83,6 +193,13 @@ impl super::AppModule for Fermentecibles {
 
         self.total_weight = weights.iter().sum();
 
+        ui.label(format!(
+            "Efficacité (%) : empâtage {mash_efficiency:.1}, ébullition {:.1}, brassage {brewhouse_efficiency:.1}",
+            self.kettle_efficiency
+        ));
+
+        ui.add_space(DEFAULT_SPACING);
+
         ui.horizontal(|ui| {
             for (index, fermentecible) in &mut self.fermentecibles.iter_mut().enumerate() {
                 ui.vertical(|ui| {
@@ -112,6 +229,22 @@ fn fermentecible_ui(ui: &mut Ui, index: usize, fermentecible: &mut Fermentecible
                 .show(ui, |ui| {
                     ui.text_edit_singleline(&mut fermentecible.name);
                     ui.add_space(DEFAULT_SPACING);
+                    ComboBox::from_id_salt(("fermentescible-type", index))
+                        .selected_text(fermentecible.malt_type.label())
+                        .show_ui(ui, |ui| {
+                            for malt_type in MaltType::ALL {
+                                ui.selectable_value(
+                                    &mut fermentecible.malt_type,
+                                    malt_type,
+                                    malt_type.label(),
+                                );
+                            }
+                        });
+                    ui.checkbox(
+                        &mut fermentecible.added_to_boil,
+                        "Ajouté à l'ébullition (non empâté)",
+                    );
+                    ui.add_space(DEFAULT_SPACING);
                     ui.label("Extrait (%)");
                     ui.add(Slider::new(&mut fermentecible.extract, 0.0..=100.0));
                     ui.add_space(DEFAULT_SPACING);
@@ -1,3 +1,4 @@
+use crate::app::modules::ingredients_index::{IngredientKind, IngredientUse};
 use crate::app::modules::math;
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
@@ -24,6 +25,19 @@ pub struct BoilHops {
     pub original_gravity: f32,
 }
 
+impl BoilHops {
+    pub fn ingredient_uses(&self) -> Vec<IngredientUse> {
+        self.hops
+            .iter()
+            .map(|hop| IngredientUse {
+                kind: IngredientKind::Hop,
+                name: hop.name.clone(),
+                weight: hop.weight,
+            })
+            .collect()
+    }
+}
+
 impl super::super::AppModule for BoilHops {
     fn new() -> Self {
         Self {
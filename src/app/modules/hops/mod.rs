@@ -1,6 +1,7 @@
 mod boil;
 mod whirlpool;
 
+use crate::app::modules::ingredients_index::IngredientUse;
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
@@ -15,6 +16,14 @@ pub struct Hops {
     pub batch_size: u16,
 }
 
+impl Hops {
+    pub fn ingredient_uses(&self) -> Vec<IngredientUse> {
+        let mut uses = self.whirlpool.ingredient_uses();
+        uses.extend(self.boil.ingredient_uses());
+        uses
+    }
+}
+
 impl super::AppModule for Hops {
     fn new() -> Self {
         Self {
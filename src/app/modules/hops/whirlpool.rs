@@ -1,3 +1,4 @@
+use crate::app::modules::ingredients_index::{IngredientKind, IngredientUse};
 use crate::app::modules::math;
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
@@ -23,6 +24,19 @@ pub struct WhirlpoolHops {
     pub batch_size: u16,
 }
 
+impl WhirlpoolHops {
+    pub fn ingredient_uses(&self) -> Vec<IngredientUse> {
+        self.hops
+            .iter()
+            .map(|hop| IngredientUse {
+                kind: IngredientKind::Hop,
+                name: hop.name.clone(),
+                weight: hop.weight,
+            })
+            .collect()
+    }
+}
+
 impl super::super::AppModule for WhirlpoolHops {
     fn new() -> Self {
         Self {
@@ -0,0 +1,58 @@
+use egui::emath::Numeric;
+use egui::*;
+
+// Edit widgets for user-defined entries, they return true when the value changed
+
+pub fn text_field(ui: &mut Ui, label: &str, value: &mut Option<String>) -> bool {
+    let mut text = value.clone().unwrap_or_default();
+
+    let changed = ui
+        .horizontal(|ui| {
+            ui.label(label);
+            ui.text_edit_singleline(&mut text).changed()
+        })
+        .inner;
+
+    if changed {
+        *value = (!text.trim().is_empty()).then_some(text);
+    }
+
+    changed
+}
+
+pub fn number_field<T: Numeric + Default>(ui: &mut Ui, label: &str, value: &mut Option<T>) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        optional_number_ui(ui, value)
+    })
+    .inner
+}
+
+pub fn range_field<T: Numeric + Default>(
+    ui: &mut Ui,
+    label: &str,
+    min: &mut Option<T>,
+    max: &mut Option<T>,
+) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let changed = optional_number_ui(ui, min);
+        ui.label("-");
+        optional_number_ui(ui, max) || changed
+    })
+    .inner
+}
+
+fn optional_number_ui<T: Numeric + Default>(ui: &mut Ui, value: &mut Option<T>) -> bool {
+    let mut enabled = value.is_some();
+    let mut number = value.unwrap_or_default();
+
+    let mut changed = ui.checkbox(&mut enabled, "").changed();
+    changed |= ui
+        .add_enabled(enabled, DragValue::new(&mut number).speed(0.1))
+        .changed();
+
+    *value = enabled.then_some(number);
+
+    changed
+}
@@ -1,3 +1,5 @@
+use super::custom::{range_field, text_field};
+use super::table::{range_of, Column, IndexEntry, TableState};
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
@@ -13,6 +15,9 @@ pub const JSON_FILE: &[u8; 66023] = include_bytes!("assets/hops.json");
 pub struct HopIng {
     #[serde(skip)]
     opened: bool,
+    /// User-defined entry, editable and not part of BrewDB
+    #[serde(default)]
+    pub custom: bool,
     id: Option<i64>,
     brewing_usage: Option<String>,
     name: String,
@@ -35,6 +40,40 @@ pub struct HopIng {
 pub struct HopsIndex {
     pub hops: Vec<HopIng>,
     opened: bool,
+    #[serde(default)]
+    table: TableState,
+}
+
+impl IndexEntry for HopIng {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.info.as_deref()
+    }
+
+    fn columns() -> Vec<Column<Self>> {
+        vec![
+            Column::Range("Alpha (%)", |hop| range_of(hop.alpha_min, hop.alpha_max)),
+            Column::Range("Beta (%)", |hop| range_of(hop.beta_min, hop.beta_max)),
+            Column::Range("Cohumulone (%)", |hop| {
+                range_of(hop.cohumulone_min, hop.cohumulone_max)
+            }),
+            Column::Range("Huiles (mL/100g)", |hop| {
+                range_of(hop.total_oil_min, hop.total_oil_max)
+            }),
+            Column::Text("Usage", |hop| hop.brewing_usage.as_deref()),
+            Column::Text("Origine", |hop| hop.pedigree.as_deref()),
+            Column::Text("Source", |hop| {
+                Some(if hop.custom { "Perso" } else { "BrewDB" })
+            }),
+        ]
+    }
+
+    fn toggle(&mut self) {
+        self.opened = !self.opened;
+    }
 }
 
 impl HopsIndex {
@@ -59,120 +98,178 @@ impl HopsIndex {
             .default_size([400., 400.])
             .open(&mut self.opened)
             .show(ui.ctx(), |ui| {
-                ScrollArea::vertical()
-                    .id_salt("liste-houblons")
-                    .show(ui, |ui| {
-                        for hop in &mut self.hops {
-                            if ui.button(&hop.name).clicked() {
-                                hop.opened = !hop.opened;
+                if ui.button("Ajouter un houblon").clicked() {
+                    self.hops.push(HopIng {
+                        name: "Nouveau houblon".into(),
+                        custom: true,
+                        opened: true,
+                        ..Default::default()
+                    });
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.table.show(ui, "liste-houblons", &mut self.hops);
+            });
+
+        let mut deleted = None;
+
+        for (index, hop) in self.hops.iter_mut().enumerate() {
+            let mut opened = hop.opened;
+
+            Window::new(&hop.name)
+                .id(Id::new(("houblon", index)))
+                .default_size([400., 400.])
+                .open(&mut opened)
+                .show(ui.ctx(), |ui| {
+                    ScrollArea::vertical()
+                        .id_salt(("houblon", index))
+                        .show(ui, |ui| {
+                            if hop.custom {
+                                custom_hop_ui(ui, hop);
+
+                                ui.add_space(DEFAULT_SPACING);
+
+                                if ui.button("Supprimer").clicked() {
+                                    deleted = Some(index);
+                                }
+
+                                return;
                             }
 
-                            Window::new(&hop.name)
-                                .default_size([400., 400.])
-                                .open(&mut hop.opened)
-                                .show(ui.ctx(), |ui| {
-                                    ScrollArea::vertical().id_salt(&hop.name).show(ui, |ui| {
-                                        if let Some(info) = &hop.info {
-                                            ui.label(info);
-                                        };
+                            if let Some(info) = &hop.info {
+                                ui.label(info);
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(brewing_usage) = &hop.brewing_usage {
-                                            ui.label(format!("Brewing usage: {brewing_usage}"));
-                                        };
+                            if let Some(brewing_usage) = &hop.brewing_usage {
+                                ui.label(format!("Brewing usage: {brewing_usage}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(aroma) = &hop.aroma {
-                                            ui.label(format!("Aroma: {aroma}"));
-                                        };
+                            if let Some(aroma) = &hop.aroma {
+                                ui.label(format!("Aroma: {aroma}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(pedigree) = &hop.pedigree {
-                                            ui.label(format!("Pedigree: {pedigree}"));
-                                        };
+                            if let Some(pedigree) = &hop.pedigree {
+                                ui.label(format!("Pedigree: {pedigree}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Alpha");
+                            ui.horizontal(|ui| {
+                                ui.label("Alpha");
 
-                                            if let Some(alpha_min) = &hop.alpha_min {
-                                                ui.label(format!("{alpha_min}"));
-                                            };
+                                if let Some(alpha_min) = &hop.alpha_min {
+                                    ui.label(format!("{alpha_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(alpha_max) = &hop.alpha_max {
-                                                ui.label(format!("{alpha_max}"));
-                                            };
-                                        });
+                                if let Some(alpha_max) = &hop.alpha_max {
+                                    ui.label(format!("{alpha_max}"));
+                                };
+                            });
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Beta");
+                            ui.horizontal(|ui| {
+                                ui.label("Beta");
 
-                                            if let Some(beta_min) = &hop.beta_min {
-                                                ui.label(format!("{beta_min}"));
-                                            };
+                                if let Some(beta_min) = &hop.beta_min {
+                                    ui.label(format!("{beta_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(beta_max) = &hop.beta_max {
-                                                ui.label(format!("{beta_max}"));
-                                            };
-                                        });
+                                if let Some(beta_max) = &hop.beta_max {
+                                    ui.label(format!("{beta_max}"));
+                                };
+                            });
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Cohumulone");
+                            ui.horizontal(|ui| {
+                                ui.label("Cohumulone");
 
-                                            if let Some(cohumulone_min) = &hop.cohumulone_min {
-                                                ui.label(format!("{cohumulone_min}"));
-                                            };
+                                if let Some(cohumulone_min) = &hop.cohumulone_min {
+                                    ui.label(format!("{cohumulone_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(cohumulone_max) = &hop.cohumulone_max {
-                                                ui.label(format!("{cohumulone_max}"));
-                                            };
-                                        });
+                                if let Some(cohumulone_max) = &hop.cohumulone_max {
+                                    ui.label(format!("{cohumulone_max}"));
+                                };
+                            });
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Total oil");
+                            ui.horizontal(|ui| {
+                                ui.label("Total oil");
 
-                                            if let Some(total_oil_min) = &hop.total_oil_min {
-                                                ui.label(format!("{total_oil_min}"));
-                                            };
+                                if let Some(total_oil_min) = &hop.total_oil_min {
+                                    ui.label(format!("{total_oil_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(total_oil_max) = &hop.total_oil_max {
-                                                ui.label(format!("{total_oil_max}"));
-                                            };
-                                        });
+                                if let Some(total_oil_max) = &hop.total_oil_max {
+                                    ui.label(format!("{total_oil_max}"));
+                                };
+                            });
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(styles) = &hop.styles {
-                                            ui.label(format!("Styles: {styles}"));
-                                        };
+                            if let Some(styles) = &hop.styles {
+                                ui.label(format!("Styles: {styles}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(trade) = &hop.trade {
-                                            ui.label(format!("Trade: {trade}"));
-                                        };
-                                    });
-                                });
-                        }
-                    });
-            });
+                            if let Some(trade) = &hop.trade {
+                                ui.label(format!("Trade: {trade}"));
+                            };
+                        });
+                });
+
+            hop.opened = opened;
+        }
+
+        if let Some(index) = deleted {
+            self.hops.remove(index);
+        }
     }
 }
+
+fn custom_hop_ui(ui: &mut Ui, hop: &mut HopIng) {
+    ui.horizontal(|ui| {
+        ui.label("Nom");
+        ui.text_edit_singleline(&mut hop.name);
+    });
+
+    text_field(ui, "Info", &mut hop.info);
+    text_field(ui, "Usage", &mut hop.brewing_usage);
+    text_field(ui, "Arôme", &mut hop.aroma);
+    text_field(ui, "Origine", &mut hop.pedigree);
+    range_field(ui, "Alpha (%)", &mut hop.alpha_min, &mut hop.alpha_max);
+    range_field(ui, "Beta (%)", &mut hop.beta_min, &mut hop.beta_max);
+    range_field(
+        ui,
+        "Cohumulone (%)",
+        &mut hop.cohumulone_min,
+        &mut hop.cohumulone_max,
+    );
+    range_field(
+        ui,
+        "Huiles (mL/100g)",
+        &mut hop.total_oil_min,
+        &mut hop.total_oil_max,
+    );
+    text_field(ui, "Styles", &mut hop.styles);
+    text_field(ui, "Substituts", &mut hop.trade);
+}
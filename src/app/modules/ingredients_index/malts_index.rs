@@ -1,3 +1,5 @@
+use super::custom::{number_field, range_field, text_field};
+use super::table::{range_of, Column, IndexEntry, TableState};
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
@@ -13,6 +15,9 @@ pub const JSON_FILE: &[u8; 44049] = include_bytes!("assets/malts.json");
 pub struct MaltIng {
     #[serde(skip)]
     opened: bool,
+    /// User-defined entry, editable and not part of BrewDB
+    #[serde(default)]
+    pub custom: bool,
     pub id: Option<i64>,
     pub name: String,
     pub description: Option<String>,
@@ -33,6 +38,41 @@ pub struct MaltIng {
 pub struct MaltsIndex {
     pub malts: Vec<MaltIng>,
     opened: bool,
+    #[serde(default)]
+    table: TableState,
+}
+
+impl IndexEntry for MaltIng {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn columns() -> Vec<Column<Self>> {
+        vec![
+            Column::Range("EBC", |malt| range_of(malt.ebc_min, malt.ebc_max)),
+            Column::Range("Pouvoir diastasique", |malt| {
+                range_of(malt.diastatic_power.map(|power| power as f32), None)
+            }),
+            Column::Range("Rendement (%)", |malt| range_of(malt.grain_yield, None)),
+            Column::Range("Humidité (%)", |malt| range_of(malt.moisture, None)),
+            Column::Range("Protéines (%)", |malt| range_of(malt.total_protein, None)),
+            Column::Range("Kolbach", |malt| {
+                range_of(malt.kolbach_index.map(|index| index as f32), None)
+            }),
+            Column::Text("Malterie", |malt| malt.maltster.as_deref()),
+            Column::Text("Source", |malt| {
+                Some(if malt.custom { "Perso" } else { "BrewDB" })
+            }),
+        ]
+    }
+
+    fn toggle(&mut self) {
+        self.opened = !self.opened;
+    }
 }
 
 impl MaltsIndex {
@@ -57,96 +97,145 @@ impl MaltsIndex {
             .default_size([400., 400.])
             .open(&mut self.opened)
             .show(ui.ctx(), |ui| {
-                ScrollArea::vertical()
-                    .id_salt("liste-malts")
-                    .show(ui, |ui| {
-                        for malt in &mut self.malts {
-                            if ui.button(&malt.name).clicked() {
-                                malt.opened = !malt.opened;
+                if ui.button("Ajouter un malt").clicked() {
+                    self.malts.push(MaltIng {
+                        name: "Nouveau malt".into(),
+                        custom: true,
+                        opened: true,
+                        ..Default::default()
+                    });
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.table.show(ui, "liste-malts", &mut self.malts);
+            });
+
+        let mut deleted = None;
+
+        for (index, malt) in self.malts.iter_mut().enumerate() {
+            let mut opened = malt.opened;
+
+            Window::new(&malt.name)
+                .id(Id::new(("malt", index)))
+                .default_size([400., 400.])
+                .open(&mut opened)
+                .show(ui.ctx(), |ui| {
+                    ScrollArea::vertical()
+                        .id_salt(("malt", index))
+                        .show(ui, |ui| {
+                            if malt.custom {
+                                custom_malt_ui(ui, malt);
+
+                                ui.add_space(DEFAULT_SPACING);
+
+                                if ui.button("Supprimer").clicked() {
+                                    deleted = Some(index);
+                                }
+
+                                return;
                             }
 
-                            Window::new(&malt.name)
-                                .default_size([400., 400.])
-                                .open(&mut malt.opened)
-                                .show(ui.ctx(), |ui| {
-                                    ScrollArea::vertical().id_salt(&malt.name).show(ui, |ui| {
-                                        if let Some(description) = &malt.description {
-                                            ui.label(description);
-                                        };
+                            if let Some(description) = &malt.description {
+                                ui.label(description);
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(maltster) = &malt.maltster {
-                                            ui.label(format!("Maltster: {maltster}"));
-                                        };
+                            if let Some(maltster) = &malt.maltster {
+                                ui.label(format!("Maltster: {maltster}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(ratio) = &malt.ratio {
-                                            ui.label(format!("Ratio: {ratio}"));
-                                        };
+                            if let Some(ratio) = &malt.ratio {
+                                ui.label(format!("Ratio: {ratio}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(grain_yield) = &malt.grain_yield {
-                                            ui.label(format!("Grain yield: {grain_yield}"));
-                                        };
+                            if let Some(grain_yield) = &malt.grain_yield {
+                                ui.label(format!("Grain yield: {grain_yield}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(moisture) = &malt.moisture {
-                                            ui.label(format!("Moisture: {moisture}"));
-                                        };
+                            if let Some(moisture) = &malt.moisture {
+                                ui.label(format!("Moisture: {moisture}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(diastatic_power) = &malt.diastatic_power {
-                                            ui.label(format!("Diastatic power: {diastatic_power}"));
-                                        };
+                            if let Some(diastatic_power) = &malt.diastatic_power {
+                                ui.label(format!("Diastatic power: {diastatic_power}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(kolbach_index) = &malt.kolbach_index {
-                                            ui.label(format!("Kolbach index: {kolbach_index}"));
-                                        };
+                            if let Some(kolbach_index) = &malt.kolbach_index {
+                                ui.label(format!("Kolbach index: {kolbach_index}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(total_nitrogen) = &malt.total_nitrogen {
-                                            ui.label(format!("Total nitrogen: {total_nitrogen}"));
-                                        };
+                            if let Some(total_nitrogen) = &malt.total_nitrogen {
+                                ui.label(format!("Total nitrogen: {total_nitrogen}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(total_protein) = &malt.total_protein {
-                                            ui.label(format!("Total protein: {total_protein}"));
-                                        };
+                            if let Some(total_protein) = &malt.total_protein {
+                                ui.label(format!("Total protein: {total_protein}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(snr) = &malt.snr {
-                                            ui.label(format!("SNR: {snr}"));
-                                        };
+                            if let Some(snr) = &malt.snr {
+                                ui.label(format!("SNR: {snr}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("EBC");
+                            ui.horizontal(|ui| {
+                                ui.label("EBC");
 
-                                            if let Some(ebc_min) = &malt.ebc_min {
-                                                ui.label(format!("{ebc_min}"));
-                                            };
+                                if let Some(ebc_min) = &malt.ebc_min {
+                                    ui.label(format!("{ebc_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(ebc_max) = &malt.ebc_max {
-                                                ui.label(format!("{ebc_max}"));
-                                            };
-                                        });
-                                    });
-                                });
-                        }
-                    });
-            });
+                                if let Some(ebc_max) = &malt.ebc_max {
+                                    ui.label(format!("{ebc_max}"));
+                                };
+                            });
+                        });
+                });
+
+            malt.opened = opened;
+        }
+
+        if let Some(index) = deleted {
+            self.malts.remove(index);
+        }
     }
 }
+
+fn custom_malt_ui(ui: &mut Ui, malt: &mut MaltIng) {
+    ui.horizontal(|ui| {
+        ui.label("Nom");
+        ui.text_edit_singleline(&mut malt.name);
+    });
+
+    text_field(ui, "Description", &mut malt.description);
+    text_field(ui, "Malterie", &mut malt.maltster);
+    range_field(ui, "EBC", &mut malt.ebc_min, &mut malt.ebc_max);
+    number_field(ui, "Rendement (%)", &mut malt.grain_yield);
+    number_field(ui, "Humidité (%)", &mut malt.moisture);
+    number_field(ui, "Pouvoir diastasique", &mut malt.diastatic_power);
+    number_field(ui, "Indice de Kolbach", &mut malt.kolbach_index);
+    number_field(ui, "Azote total (%)", &mut malt.total_nitrogen);
+    number_field(ui, "Protéines (%)", &mut malt.total_protein);
+    number_field(ui, "SNR", &mut malt.snr);
+    number_field(ui, "Ratio max (%)", &mut malt.ratio);
+}
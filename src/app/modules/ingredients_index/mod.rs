@@ -1,10 +1,53 @@
+mod custom;
 mod hops_index;
 mod malts_index;
-mod yeasts_index;
+mod table;
+pub mod yeasts_index;
 
 use eframe::*;
 use egui::*;
 use serde::{Deserialize, Serialize};
+use table::IndexEntry;
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IngredientKind {
+    #[default]
+    Malt,
+    Hop,
+    Yeast,
+}
+
+impl IngredientKind {
+    pub const ALL: [IngredientKind; 3] = [Self::Malt, Self::Hop, Self::Yeast];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Malt => "Malt",
+            Self::Hop => "Houblon",
+            Self::Yeast => "Ferment",
+        }
+    }
+}
+
+/// Recipe, stock and price entries are matched by kind and case-insensitive name
+///
+pub fn same_ingredient(
+    kind: IngredientKind,
+    name: &str,
+    other_kind: IngredientKind,
+    other_name: &str,
+) -> bool {
+    kind == other_kind && name.trim().eq_ignore_ascii_case(other_name.trim())
+}
+
+/// Weight in g of an ingredient used by the current recipe
+#[derive(Deserialize, Serialize, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct IngredientUse {
+    pub kind: IngredientKind,
+    pub name: String,
+    pub weight: f32,
+}
 
 #[derive(Deserialize, Serialize, Default)]
 pub struct IngredientsIndex {
@@ -13,6 +56,26 @@ pub struct IngredientsIndex {
     yeasts: yeasts_index::YeastsIndex,
 }
 
+impl IngredientsIndex {
+    pub fn yeasts(&self) -> &[yeasts_index::YeastIng] {
+        &self.yeasts.yeasts
+    }
+
+    pub fn names(&self, kind: IngredientKind) -> Vec<&str> {
+        match kind {
+            IngredientKind::Malt => self.malts.malts.iter().map(IndexEntry::name).collect(),
+            IngredientKind::Hop => self.hops.hops.iter().map(IndexEntry::name).collect(),
+            IngredientKind::Yeast => self.yeasts.yeasts.iter().map(IndexEntry::name).collect(),
+        }
+    }
+
+    /// Returns true once after user-defined yeasts changed
+    ///
+    pub fn take_yeasts_changed(&mut self) -> bool {
+        std::mem::take(&mut self.yeasts.changed)
+    }
+}
+
 impl super::AppModule for IngredientsIndex {
     fn new() -> Self {
         #[cfg(not(target_arch = "wasm32"))]
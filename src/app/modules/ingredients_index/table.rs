@@ -0,0 +1,207 @@
+use crate::app::modules::range_filter::RangeFilter;
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+pub enum Column<T> {
+    Text(&'static str, fn(&T) -> Option<&str>),
+    /// Single values are shown as a (value, value) range
+    Range(&'static str, fn(&T) -> Option<(f32, f32)>),
+}
+
+impl<T> Column<T> {
+    fn title(&self) -> &'static str {
+        match self {
+            Column::Text(title, _) | Column::Range(title, _) => title,
+        }
+    }
+
+    /// Missing values always end up last, whatever the direction
+    ///
+    fn compare(&self, a: &T, b: &T, ascending: bool) -> Ordering {
+        let directed = |ordering: Ordering| {
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        };
+
+        match self {
+            Column::Text(_, value) => match (value(a), value(b)) {
+                (Some(a), Some(b)) => directed(a.to_lowercase().cmp(&b.to_lowercase())),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+            Column::Range(_, value) => match (value(a), value(b)) {
+                (Some((a, _)), Some((b, _))) => directed(a.total_cmp(&b)),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            },
+        }
+    }
+
+    fn format(&self, entry: &T) -> String {
+        match self {
+            Column::Text(_, value) => value(entry).unwrap_or_default().to_string(),
+            Column::Range(_, value) => match value(entry) {
+                Some((min, max)) if min == max => format!("{min}"),
+                Some((min, max)) => format!("{min} - {max}"),
+                None => String::new(),
+            },
+        }
+    }
+}
+
+/// Builds a column range out of optional bounds, a lone bound gives a single value
+///
+pub fn range_of(min: Option<f32>, max: Option<f32>) -> Option<(f32, f32)> {
+    match (min, max) {
+        (Some(min), Some(max)) => Some((min, max)),
+        (Some(value), None) | (None, Some(value)) => Some((value, value)),
+        (None, None) => None,
+    }
+}
+
+/// An ingredient that can be listed in an index table
+pub trait IndexEntry {
+    fn name(&self) -> &str;
+    /// Free text fields looked up by the search, on top of the name and text columns
+    fn description(&self) -> Option<&str>;
+    fn columns() -> Vec<Column<Self>>
+    where
+        Self: Sized;
+    fn toggle(&mut self);
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct TableState {
+    search: String,
+    /// Column index, ascending
+    sort: Option<(usize, bool)>,
+    filters: Vec<RangeFilter>,
+}
+
+impl TableState {
+    pub fn show<T: IndexEntry>(&mut self, ui: &mut Ui, id_salt: &str, entries: &mut [T]) {
+        let columns = T::columns();
+
+        self.filters.resize(columns.len(), RangeFilter::default());
+
+        ui.horizontal(|ui| {
+            ui.label("Recherche : ");
+            ui.text_edit_singleline(&mut self.search);
+        });
+
+        CollapsingHeader::new("Filtres")
+            .id_salt(format!("{id_salt}-filtres"))
+            .show(ui, |ui| {
+                for (column, filter) in columns.iter().zip(&mut self.filters) {
+                    if let Column::Range(title, _) = column {
+                        filter.show(ui, title, 0.1);
+                    }
+                }
+            });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let rows = self.rows(&columns, entries);
+
+        ui.label(format!("{} / {}", rows.len(), entries.len()));
+
+        ScrollArea::both().id_salt(id_salt).show(ui, |ui| {
+            Grid::new(id_salt).striped(true).show(ui, |ui| {
+                if ui.button(self.header("Nom", None)).clicked() {
+                    self.sort = None;
+                }
+
+                for (index, column) in columns.iter().enumerate() {
+                    if ui
+                        .button(self.header(column.title(), Some(index)))
+                        .clicked()
+                    {
+                        self.sort = match self.sort {
+                            Some((sorted, true)) if sorted == index => Some((index, false)),
+                            _ => Some((index, true)),
+                        };
+                    }
+                }
+
+                ui.end_row();
+
+                for row in rows {
+                    let entry = &mut entries[row];
+
+                    if ui.link(entry.name()).clicked() {
+                        entry.toggle();
+                    }
+
+                    for column in &columns {
+                        ui.label(column.format(entry));
+                    }
+
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    fn header(&self, title: &str, column: Option<usize>) -> String {
+        match self.sort {
+            Some((sorted, ascending)) if Some(sorted) == column => {
+                format!("{title} {}", if ascending { "⬆" } else { "⬇" })
+            }
+            None if column.is_none() => format!("{title} ⬆"),
+            _ => title.to_string(),
+        }
+    }
+
+    /// Indices of the entries matching the search and filters, sorted
+    ///
+    fn rows<T: IndexEntry>(&self, columns: &[Column<T>], entries: &[T]) -> Vec<usize> {
+        let terms: Vec<String> = self
+            .search
+            .to_lowercase()
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        let mut rows: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                let mut texts = vec![entry.name()];
+                texts.extend(entry.description());
+                texts.extend(columns.iter().filter_map(|column| match column {
+                    Column::Text(_, value) => value(entry),
+                    Column::Range(..) => None,
+                }));
+
+                let texts: Vec<String> = texts.iter().map(|text| text.to_lowercase()).collect();
+
+                terms
+                    .iter()
+                    .all(|term| texts.iter().any(|text| text.contains(term.as_str())))
+            })
+            .filter(|(_, entry)| {
+                columns
+                    .iter()
+                    .zip(&self.filters)
+                    .all(|(column, filter)| match column {
+                        Column::Range(_, value) => filter.overlaps(value(entry)),
+                        Column::Text(..) => true,
+                    })
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        match self.sort {
+            Some((column, ascending)) if column < columns.len() => {
+                rows.sort_by(|&a, &b| columns[column].compare(&entries[a], &entries[b], ascending));
+            }
+            _ => rows.sort_by_key(|&row| entries[row].name().to_lowercase()),
+        }
+
+        rows
+    }
+}
@@ -1,3 +1,5 @@
+use super::custom::{number_field, range_field, text_field};
+use super::table::{range_of, Column, IndexEntry, TableState};
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
@@ -9,10 +11,13 @@ pub const JSON_PATH: &str = "src/app/modules/ingredients_index/assets/yeasts.jso
 #[cfg(target_arch = "wasm32")]
 pub const JSON_FILE: &[u8; 6089] = include_bytes!("assets/yeasts.json");
 
-#[derive(Deserialize, Serialize, Default)]
+#[derive(Deserialize, Serialize, Default, Clone)]
 pub struct YeastIng {
     #[serde(skip)]
     opened: bool,
+    /// User-defined entry, editable and not part of BrewDB
+    #[serde(default)]
+    pub custom: bool,
     pub id: Option<i64>,
     pub name: String,
     pub description: Option<String>,
@@ -31,6 +36,51 @@ pub struct YeastIng {
 pub struct YeastsIndex {
     pub yeasts: Vec<YeastIng>,
     opened: bool,
+    #[serde(default)]
+    table: TableState,
+    /// Set when user-defined entries are added, edited or deleted
+    #[serde(skip)]
+    pub changed: bool,
+}
+
+impl IndexEntry for YeastIng {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn columns() -> Vec<Column<Self>> {
+        vec![
+            Column::Range("Atténuation (%)", |yeast| {
+                range_of(
+                    yeast.attenuation_min.map(|attenuation| attenuation as f32),
+                    yeast.attenuation_max.map(|attenuation| attenuation as f32),
+                )
+            }),
+            Column::Range("Température (°C)", |yeast| {
+                range_of(
+                    yeast.temp_min.map(|temp| temp as f32),
+                    yeast.temp_max.map(|temp| temp as f32),
+                )
+            }),
+            Column::Range("Tolérance alcool (%)", |yeast| {
+                range_of(yeast.alcohol_tolerance, None)
+            }),
+            Column::Text("Labo", |yeast| yeast.lab.as_deref()),
+            Column::Text("Forme", |yeast| yeast.form.as_deref()),
+            Column::Text("Floculation", |yeast| yeast.flocculation.as_deref()),
+            Column::Text("Source", |yeast| {
+                Some(if yeast.custom { "Perso" } else { "BrewDB" })
+            }),
+        ]
+    }
+
+    fn toggle(&mut self) {
+        self.opened = !self.opened;
+    }
 }
 
 impl YeastsIndex {
@@ -55,88 +105,155 @@ impl YeastsIndex {
             .default_size([400., 400.])
             .open(&mut self.opened)
             .show(ui.ctx(), |ui| {
-                ScrollArea::vertical()
-                    .id_salt("liste-yeasts")
-                    .show(ui, |ui| {
-                        for yeast in &mut self.yeasts {
-                            if ui.button(&yeast.name).clicked() {
-                                yeast.opened = !yeast.opened;
+                if ui.button("Ajouter un ferment").clicked() {
+                    self.yeasts.push(YeastIng {
+                        name: "Nouveau ferment".into(),
+                        custom: true,
+                        opened: true,
+                        ..Default::default()
+                    });
+                    self.changed = true;
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.table.show(ui, "liste-yeasts", &mut self.yeasts);
+            });
+
+        let mut deleted = None;
+        let mut edited = false;
+
+        for (index, yeast) in self.yeasts.iter_mut().enumerate() {
+            let mut opened = yeast.opened;
+
+            Window::new(&yeast.name)
+                .id(Id::new(("ferment", index)))
+                .default_size([400., 400.])
+                .open(&mut opened)
+                .show(ui.ctx(), |ui| {
+                    ScrollArea::vertical()
+                        .id_salt(("ferment", index))
+                        .show(ui, |ui| {
+                            if yeast.custom {
+                                edited |= custom_yeast_ui(ui, yeast);
+
+                                ui.add_space(DEFAULT_SPACING);
+
+                                if ui.button("Supprimer").clicked() {
+                                    deleted = Some(index);
+                                }
+
+                                return;
                             }
 
-                            Window::new(&yeast.name)
-                                .default_size([400., 400.])
-                                .open(&mut yeast.opened)
-                                .show(ui.ctx(), |ui| {
-                                    ScrollArea::vertical().id_salt(&yeast.name).show(ui, |ui| {
-                                        if let Some(description) = &yeast.description {
-                                            ui.label(description);
-                                        };
+                            if let Some(description) = &yeast.description {
+                                ui.label(description);
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(styles) = &yeast.styles {
-                                            ui.label(format!("Styles: {styles}"));
-                                        };
+                            if let Some(styles) = &yeast.styles {
+                                ui.label(format!("Styles: {styles}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(alcohol_tolerance) = &yeast.alcohol_tolerance {
-                                            ui.label(format!(
-                                                "Alcohol tolerance: {alcohol_tolerance}"
-                                            ));
-                                        };
+                            if let Some(alcohol_tolerance) = &yeast.alcohol_tolerance {
+                                ui.label(format!("Alcohol tolerance: {alcohol_tolerance}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(flocculation) = &yeast.flocculation {
-                                            ui.label(format!("Flocculation: {flocculation}"));
-                                        };
+                            if let Some(flocculation) = &yeast.flocculation {
+                                ui.label(format!("Flocculation: {flocculation}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Attenuation");
+                            ui.horizontal(|ui| {
+                                ui.label("Attenuation");
 
-                                            if let Some(attenuation_min) = &yeast.attenuation_min {
-                                                ui.label(format!("{attenuation_min}"));
-                                            };
+                                if let Some(attenuation_min) = &yeast.attenuation_min {
+                                    ui.label(format!("{attenuation_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(attenuation_max) = &yeast.attenuation_max {
-                                                ui.label(format!("{attenuation_max}"));
-                                            };
-                                        });
+                                if let Some(attenuation_max) = &yeast.attenuation_max {
+                                    ui.label(format!("{attenuation_max}"));
+                                };
+                            });
 
-                                        ui.horizontal(|ui| {
-                                            ui.label("Temperature");
+                            ui.horizontal(|ui| {
+                                ui.label("Temperature");
 
-                                            if let Some(temp_min) = &yeast.temp_min {
-                                                ui.label(format!("{temp_min}"));
-                                            };
+                                if let Some(temp_min) = &yeast.temp_min {
+                                    ui.label(format!("{temp_min}"));
+                                };
 
-                                            ui.label("-");
+                                ui.label("-");
 
-                                            if let Some(temp_max) = &yeast.temp_max {
-                                                ui.label(format!("{temp_max}"));
-                                            };
-                                        });
+                                if let Some(temp_max) = &yeast.temp_max {
+                                    ui.label(format!("{temp_max}"));
+                                };
+                            });
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(lab) = &yeast.lab {
-                                            ui.label(format!("Lab: {lab}"));
-                                        };
+                            if let Some(lab) = &yeast.lab {
+                                ui.label(format!("Lab: {lab}"));
+                            };
 
-                                        ui.add_space(DEFAULT_SPACING);
+                            ui.add_space(DEFAULT_SPACING);
 
-                                        if let Some(form) = &yeast.form {
-                                            ui.label(format!("Form: {form}"));
-                                        };
-                                    });
-                                });
-                        }
-                    });
-            });
+                            if let Some(form) = &yeast.form {
+                                ui.label(format!("Form: {form}"));
+                            };
+                        });
+                });
+
+            yeast.opened = opened;
+        }
+
+        if let Some(index) = deleted {
+            self.yeasts.remove(index);
+            self.changed = true;
+        }
+
+        if edited {
+            self.changed = true;
+        }
     }
 }
+
+/// Returns true when one of the fields changed
+///
+fn custom_yeast_ui(ui: &mut Ui, yeast: &mut YeastIng) -> bool {
+    let mut changed = ui
+        .horizontal(|ui| {
+            ui.label("Nom");
+            ui.text_edit_singleline(&mut yeast.name).changed()
+        })
+        .inner;
+
+    changed |= text_field(ui, "Description", &mut yeast.description);
+    changed |= text_field(ui, "Labo", &mut yeast.lab);
+    changed |= text_field(ui, "Forme", &mut yeast.form);
+    changed |= text_field(ui, "Floculation", &mut yeast.flocculation);
+    changed |= range_field(
+        ui,
+        "Atténuation (%)",
+        &mut yeast.attenuation_min,
+        &mut yeast.attenuation_max,
+    );
+    changed |= range_field(
+        ui,
+        "Température (°C)",
+        &mut yeast.temp_min,
+        &mut yeast.temp_max,
+    );
+    changed |= number_field(ui, "Tolérance alcool (%)", &mut yeast.alcohol_tolerance);
+    changed |= text_field(ui, "Styles", &mut yeast.styles);
+
+    changed
+}
@@ -0,0 +1,281 @@
+use crate::app::modules::ingredients_index::{
+    self, IngredientKind, IngredientUse, IngredientsIndex,
+};
+use crate::app::modules::ui_defaults::*;
+use eframe::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+mod shopping;
+
+/// A lot of an ingredient in stock, quantity in g
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct StockItem {
+    pub kind: IngredientKind,
+    pub name: String,
+    pub quantity: f32,
+    pub lot: String,
+    /// Free form dates, sorted as text so YYYY-MM-DD is expected
+    pub purchase_date: String,
+    pub best_before: String,
+}
+
+/// Outcome of a brew for one recipe ingredient
+pub struct Deduction {
+    pub kind: IngredientKind,
+    pub name: String,
+    pub deducted: f32,
+    pub missing: f32,
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct Inventory {
+    opened: bool,
+    pub items: Vec<StockItem>,
+    #[serde(skip)]
+    pub recipe_uses: Vec<IngredientUse>,
+    #[serde(skip)]
+    report: Vec<Deduction>,
+    /// Recipe last taken out of stock, brewing it again needs a change first
+    brewed: Vec<IngredientUse>,
+    shopping: shopping::ShoppingList,
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    /// Quantity in g available for an ingredient, all lots together
+    ///
+    pub fn available(&self, kind: IngredientKind, name: &str) -> f32 {
+        self.items
+            .iter()
+            .filter(|item| item.matches(kind, name))
+            .map(|item| item.quantity)
+            .sum()
+    }
+
+    /// Takes the recipe ingredients out of stock, lots closest to their best before date first
+    /// Emptied lots are removed
+    ///
+    pub fn brew(&mut self, uses: &[IngredientUse]) -> Vec<Deduction> {
+        let mut report = vec![];
+
+        for ingredient in uses.iter().filter(|ingredient| ingredient.weight > 0.) {
+            let mut lots: Vec<usize> = (0..self.items.len())
+                .filter(|&index| self.items[index].matches(ingredient.kind, &ingredient.name))
+                .collect();
+            lots.sort_by_key(|&index| {
+                let best_before = &self.items[index].best_before;
+                (best_before.trim().is_empty(), best_before.clone())
+            });
+
+            let mut needed = ingredient.weight;
+
+            for index in lots {
+                let taken = needed.min(self.items[index].quantity);
+                self.items[index].quantity -= taken;
+                needed -= taken;
+            }
+
+            report.push(Deduction {
+                kind: ingredient.kind,
+                name: ingredient.name.clone(),
+                deducted: ingredient.weight - needed,
+                missing: needed,
+            });
+        }
+
+        self.items.retain(|item| item.quantity > 0.);
+
+        report
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, index: &IngredientsIndex) {
+        if ui.button("Inventaire").clicked() {
+            self.opened = !self.opened;
+        };
+
+        let mut opened = self.opened;
+
+        egui::Window::new("Inventaire")
+            .open(&mut opened)
+            .default_size([600., 400.])
+            .vscroll(true)
+            .show(ui.ctx(), |ui| {
+                self.stock_ui(ui, index);
+
+                ui.add_space(DEFAULT_SPACING);
+                ui.separator();
+
+                self.brew_ui(ui);
+
+                ui.add_space(DEFAULT_SPACING);
+                ui.separator();
+
+                let lines = self.shopping.lines(&self.recipe_uses, self);
+                self.shopping.show(ui, &lines);
+            });
+
+        self.opened = opened;
+    }
+
+    fn stock_ui(&mut self, ui: &mut Ui, index: &IngredientsIndex) {
+        ui.horizontal(|ui| {
+            ui.heading("Stock");
+            if ui.button("Ajouter un lot").clicked() {
+                self.items.push(StockItem::default());
+            }
+        });
+
+        let mut deleted = None;
+
+        Grid::new("inventaire").striped(true).show(ui, |ui| {
+            for title in [
+                "Type",
+                "Nom",
+                "Quantité (g)",
+                "Lot",
+                "Achat",
+                "À consommer avant",
+                "",
+            ] {
+                ui.label(title);
+            }
+            ui.end_row();
+
+            for (row, item) in self.items.iter_mut().enumerate() {
+                ComboBox::from_id_salt(("inventaire-type", row))
+                    .selected_text(item.kind.label())
+                    .show_ui(ui, |ui| {
+                        for kind in IngredientKind::ALL {
+                            ui.selectable_value(&mut item.kind, kind, kind.label());
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    ui.add(TextEdit::singleline(&mut item.name).desired_width(150.));
+                    ComboBox::from_id_salt(("inventaire-nom", row))
+                        .selected_text("")
+                        .width(20.)
+                        .show_ui(ui, |ui| {
+                            for name in index.names(item.kind) {
+                                if ui.selectable_label(item.name == name, name).clicked() {
+                                    item.name = name.to_string();
+                                }
+                            }
+                        });
+                });
+
+                ui.add(
+                    DragValue::new(&mut item.quantity)
+                        .speed(1.)
+                        .range(0.0..=f32::MAX),
+                );
+                ui.add(TextEdit::singleline(&mut item.lot).desired_width(80.));
+                ui.add(
+                    TextEdit::singleline(&mut item.purchase_date)
+                        .hint_text("AAAA-MM-JJ")
+                        .desired_width(90.),
+                );
+                ui.add(
+                    TextEdit::singleline(&mut item.best_before)
+                        .hint_text("AAAA-MM-JJ")
+                        .desired_width(90.),
+                );
+
+                if ui.button("x").clicked() {
+                    deleted = Some(row);
+                }
+
+                ui.end_row();
+            }
+        });
+
+        if let Some(row) = deleted {
+            self.items.remove(row);
+        }
+    }
+
+    fn brew_ui(&mut self, ui: &mut Ui) {
+        ui.heading("Recette");
+
+        egui::Frame::new()
+            .fill(LIGHTER_COLOR)
+            .inner_margin(DEFAULT_PADDING)
+            .corner_radius(DEFAULT_CORNER_RADIUS)
+            .show(ui, |ui| {
+                Grid::new("inventaire-recette")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Ingrédient");
+                        ui.label("Besoin (g)");
+                        ui.label("En stock (g)");
+                        ui.end_row();
+
+                        for ingredient in &self.recipe_uses {
+                            let available = self.available(ingredient.kind, &ingredient.name);
+
+                            ui.label(format!("{} - {}", ingredient.kind.label(), ingredient.name));
+                            ui.label(format!("{:.1}", ingredient.weight));
+                            if available < ingredient.weight {
+                                ui.colored_label(ERROR_COLOR, format!("{available:.1}"));
+                            } else {
+                                ui.label(format!("{available:.1}"));
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let already_brewed = !self.recipe_uses.is_empty() && self.recipe_uses == self.brewed;
+
+        if ui
+            .add_enabled(
+                !already_brewed,
+                Button::new("Marquer la recette comme brassée"),
+            )
+            .clicked()
+        {
+            let uses = std::mem::take(&mut self.recipe_uses);
+            self.report = self.brew(&uses);
+            self.brewed = uses.clone();
+            self.recipe_uses = uses;
+        }
+
+        if already_brewed {
+            ui.label("Cette recette a déjà été déduite du stock");
+        }
+
+        for deduction in &self.report {
+            let line = format!(
+                "{} - {} : {:.1} g déduits",
+                deduction.kind.label(),
+                deduction.name,
+                deduction.deducted
+            );
+
+            if deduction.missing > 0. {
+                ui.colored_label(
+                    ERROR_COLOR,
+                    format!("{line}, {:.1} g manquants", deduction.missing),
+                );
+            } else {
+                ui.colored_label(SUCCESS_COLOR, line);
+            }
+        }
+    }
+}
+
+impl StockItem {
+    pub fn matches(&self, kind: IngredientKind, name: &str) -> bool {
+        ingredients_index::same_ingredient(self.kind, &self.name, kind, name)
+    }
+}
@@ -0,0 +1,283 @@
+use super::Inventory;
+use crate::app::modules::ingredients_index::{self, IngredientKind, IngredientUse};
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+/// g, a package can't be empty
+const MIN_PACKAGE_SIZE: f32 = 0.1;
+
+/// Package size in g of a given ingredient, overriding the default of its kind
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct Package {
+    pub kind: IngredientKind,
+    pub name: String,
+    pub size: f32,
+}
+
+/// An ingredient to buy, weights in g
+pub struct ShoppingLine {
+    pub kind: IngredientKind,
+    pub name: String,
+    pub needed: f32,
+    pub available: f32,
+    pub package_size: f32,
+    pub packages: u32,
+}
+
+impl ShoppingLine {
+    pub fn missing(&self) -> f32 {
+        (self.needed - self.available).max(0.)
+    }
+
+    pub fn to_buy(&self) -> f32 {
+        self.packages as f32 * self.package_size
+    }
+}
+
+/// Package size in g of each ingredient kind
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct DefaultSizes {
+    malt: f32,
+    hop: f32,
+    yeast: f32,
+}
+
+impl Default for DefaultSizes {
+    fn default() -> Self {
+        Self {
+            malt: 1000.,
+            hop: 100.,
+            yeast: 11.5,
+        }
+    }
+}
+
+impl DefaultSizes {
+    fn get_mut(&mut self, kind: IngredientKind) -> &mut f32 {
+        match kind {
+            IngredientKind::Malt => &mut self.malt,
+            IngredientKind::Hop => &mut self.hop,
+            IngredientKind::Yeast => &mut self.yeast,
+        }
+    }
+
+    fn get(&self, kind: IngredientKind) -> f32 {
+        match kind {
+            IngredientKind::Malt => self.malt,
+            IngredientKind::Hop => self.hop,
+            IngredientKind::Yeast => self.yeast,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct ShoppingList {
+    package_sizes: DefaultSizes,
+    packages: Vec<Package>,
+}
+
+impl ShoppingList {
+    pub fn package_size(&self, kind: IngredientKind, name: &str) -> f32 {
+        self.packages
+            .iter()
+            .find(|package| {
+                ingredients_index::same_ingredient(package.kind, &package.name, kind, name)
+            })
+            .map(|package| package.size)
+            .filter(|size| *size > 0.)
+            .unwrap_or(self.package_sizes.get(kind))
+    }
+
+    /// Recipe needs grouped by ingredient, minus the stock and rounded up to whole packages
+    ///
+    pub fn lines(&self, uses: &[IngredientUse], inventory: &Inventory) -> Vec<ShoppingLine> {
+        let mut lines: Vec<ShoppingLine> = vec![];
+
+        for ingredient in uses.iter().filter(|ingredient| ingredient.weight > 0.) {
+            let same = |line: &&mut ShoppingLine| {
+                ingredients_index::same_ingredient(
+                    line.kind,
+                    &line.name,
+                    ingredient.kind,
+                    &ingredient.name,
+                )
+            };
+
+            match lines.iter_mut().find(same) {
+                Some(line) => line.needed += ingredient.weight,
+                None => lines.push(ShoppingLine {
+                    kind: ingredient.kind,
+                    name: ingredient.name.clone(),
+                    needed: ingredient.weight,
+                    available: inventory.available(ingredient.kind, &ingredient.name),
+                    package_size: self.package_size(ingredient.kind, &ingredient.name),
+                    packages: 0,
+                }),
+            }
+        }
+
+        for line in &mut lines {
+            line.packages = if line.package_size > 0. {
+                (line.missing() / line.package_size).ceil() as u32
+            } else {
+                0
+            };
+        }
+
+        lines.retain(|line| line.missing() > 0.);
+        lines.sort_by_key(|line| (line.kind, line.name.to_lowercase()));
+
+        lines
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, lines: &[ShoppingLine]) {
+        ui.heading("Liste de courses");
+
+        CollapsingHeader::new("Conditionnements")
+            .id_salt("courses-conditionnements")
+            .show(ui, |ui| self.packages_ui(ui));
+
+        ui.add_space(DEFAULT_SPACING);
+
+        if lines.is_empty() {
+            ui.label("Le stock couvre la recette");
+            return;
+        }
+
+        egui::Frame::new()
+            .fill(LIGHTER_COLOR)
+            .inner_margin(DEFAULT_PADDING)
+            .corner_radius(DEFAULT_CORNER_RADIUS)
+            .show(ui, |ui| {
+                Grid::new("courses").striped(true).show(ui, |ui| {
+                    for title in [
+                        "Ingrédient",
+                        "Manque (g)",
+                        "Conditionnement (g)",
+                        "Paquets",
+                        "À acheter (g)",
+                    ] {
+                        ui.label(title);
+                    }
+                    ui.end_row();
+
+                    for line in lines {
+                        ui.label(format!("{} - {}", line.kind.label(), line.name));
+                        ui.label(format!("{:.1}", line.missing()));
+                        ui.label(format!("{}", line.package_size));
+                        ui.label(format!("{}", line.packages));
+                        ui.label(format!("{}", line.to_buy()));
+                        ui.end_row();
+                    }
+                });
+            });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.horizontal(|ui| {
+            if ui.button("Copier en texte").clicked() {
+                ui.ctx().copy_text(to_text(lines));
+            }
+            if ui.button("Copier en CSV").clicked() {
+                ui.ctx().copy_text(to_csv(lines));
+            }
+        });
+    }
+
+    fn packages_ui(&mut self, ui: &mut Ui) {
+        for kind in IngredientKind::ALL {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} par défaut (g) : ", kind.label()));
+                ui.add(
+                    DragValue::new(self.package_sizes.get_mut(kind))
+                        .speed(0.5)
+                        .range(MIN_PACKAGE_SIZE..=f32::MAX),
+                );
+            });
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let mut deleted = None;
+
+        for (row, package) in self.packages.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ComboBox::from_id_salt(("courses-type", row))
+                    .selected_text(package.kind.label())
+                    .show_ui(ui, |ui| {
+                        for kind in IngredientKind::ALL {
+                            ui.selectable_value(&mut package.kind, kind, kind.label());
+                        }
+                    });
+                ui.add(TextEdit::singleline(&mut package.name).desired_width(150.));
+                ui.label("(g)");
+                ui.add(
+                    DragValue::new(&mut package.size)
+                        .speed(0.5)
+                        .range(MIN_PACKAGE_SIZE..=f32::MAX),
+                );
+                if ui.button("x").clicked() {
+                    deleted = Some(row);
+                }
+            });
+        }
+
+        if let Some(row) = deleted {
+            self.packages.remove(row);
+        }
+
+        if ui.button("Ajouter un conditionnement").clicked() {
+            self.packages.push(Package {
+                size: self.package_sizes.get(IngredientKind::Malt),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+pub fn to_text(lines: &[ShoppingLine]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            format!(
+                "{} x {} g - {} ({})\n",
+                line.packages,
+                line.package_size,
+                line.name,
+                line.kind.label()
+            )
+        })
+        .collect()
+}
+
+pub fn to_csv(lines: &[ShoppingLine]) -> String {
+    let mut csv = String::from("type;nom;manque_g;conditionnement_g;paquets;a_acheter_g\n");
+
+    for line in lines {
+        csv.push_str(&format!(
+            "{};{};{:.1};{};{};{}\n",
+            line.kind.label(),
+            csv_field(&line.name),
+            line.missing(),
+            line.package_size,
+            line.packages,
+            line.to_buy()
+        ));
+    }
+
+    csv
+}
+
+/// Quotes a field holding a separator, a quote or a line break
+///
+fn csv_field(field: &str) -> String {
+    if field.contains([';', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
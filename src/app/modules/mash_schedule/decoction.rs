@@ -0,0 +1,135 @@
+use super::{MashStep, StepPlan, StepType};
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+/// °C
+pub const BOIL_TEMP: f32 = 100.;
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct DecoctionSettings {
+    /// min
+    pub boil_time: u16,
+    /// °C/min while bringing the decoction to a boil
+    pub heating_rate: f32,
+}
+
+impl Default for DecoctionSettings {
+    fn default() -> Self {
+        Self {
+            boil_time: 15,
+            heating_rate: 2.,
+        }
+    }
+}
+
+impl DecoctionSettings {
+    /// min needed to bring a decoction to a boil and boil it, before it's poured back
+    ///
+    pub fn lead_time(&self, mash_temp: f32) -> u16 {
+        let heating = if self.heating_rate > 0. {
+            ((BOIL_TEMP - mash_temp) / self.heating_rate).ceil() as u16
+        } else {
+            0
+        };
+
+        heating + self.boil_time
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Ébullition des décoctions (min): ");
+            ui.add(Slider::new(&mut self.boil_time, 0..=60));
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.horizontal(|ui| {
+            ui.label("Vitesse de chauffe (°C/min): ");
+            ui.add(Slider::new(&mut self.heating_rate, 0.5..=5.));
+        });
+    }
+}
+
+pub enum Preset {
+    Single,
+    Double,
+    Triple,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 3] = [Self::Single, Self::Double, Self::Triple];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Single => "Décoction simple",
+            Self::Double => "Décoction double",
+            Self::Triple => "Décoction triple",
+        }
+    }
+
+    pub fn steps(&self) -> Vec<MashStep> {
+        let steps: &[(&str, f32, u16, StepType)] = match self {
+            Self::Single => &[
+                ("Saccharification", 63., 45, StepType::Infusion),
+                ("Alpha-amylase", 72., 20, StepType::Decoction),
+                ("Mash-out", 78., 10, StepType::DirectHeat),
+            ],
+            Self::Double => &[
+                ("Protéines", 52., 15, StepType::Infusion),
+                ("Bêta-amylase", 63., 40, StepType::Decoction),
+                ("Alpha-amylase", 72., 20, StepType::Decoction),
+                ("Mash-out", 78., 10, StepType::DirectHeat),
+            ],
+            Self::Triple => &[
+                ("Acide", 37., 20, StepType::Infusion),
+                ("Protéines", 52., 15, StepType::Decoction),
+                ("Saccharification", 65., 40, StepType::Decoction),
+                ("Mash-out", 76., 10, StepType::Decoction),
+            ],
+        };
+
+        steps
+            .iter()
+            .map(|&(name, temperature, duration, step_type)| MashStep {
+                name: name.to_string(),
+                temperature,
+                duration,
+                step_type,
+            })
+            .collect()
+    }
+}
+
+/// Brew day timeline, decoctions are pulled before the rest they heat
+///
+pub fn time_plan_ui(ui: &mut Ui, steps: &[MashStep], plans: &[StepPlan]) {
+    for (index, (step, plan)) in steps.iter().zip(plans).enumerate() {
+        let name = if step.name.is_empty() {
+            format!("Palier {}", index + 1)
+        } else {
+            step.name.clone()
+        };
+
+        if let Some(decoction_vol) = plan.decoction_vol {
+            ui.label(format!(
+                "{} min : prélever {decoction_vol:.1} L de maische épaisse, la porter à ébullition et reverser à {} min",
+                plan.start - plan.lead_time,
+                plan.start
+            ));
+        }
+
+        ui.label(format!(
+            "{} min : {name} à {:.0} °C pendant {} min",
+            plan.start, step.temperature, step.duration
+        ));
+    }
+
+    if let (Some(step), Some(plan)) = (steps.last(), plans.last()) {
+        ui.label(format!(
+            "{} min : fin de l'empâtage",
+            plan.start + step.duration
+        ));
+    }
+}
@@ -0,0 +1,384 @@
+use crate::app::modules::math;
+use crate::app::modules::ui_defaults::*;
+use eframe::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+mod decoction;
+
+/// Specific heat of the grain relative to water (cal/g.°C)
+const GRAIN_SPECIFIC_HEAT: f32 = 0.4;
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum StepType {
+    #[default]
+    Infusion,
+    DirectHeat,
+    Decoction,
+}
+
+impl StepType {
+    const ALL: [StepType; 3] = [Self::Infusion, Self::DirectHeat, Self::Decoction];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Infusion => "Infusion",
+            Self::DirectHeat => "Chauffe directe",
+            Self::Decoction => "Décoction",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct MashStep {
+    pub name: String,
+    /// °C
+    pub temperature: f32,
+    /// min
+    pub duration: u16,
+    pub step_type: StepType,
+}
+
+impl Default for MashStep {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            temperature: 66.,
+            duration: 60,
+            step_type: StepType::Infusion,
+        }
+    }
+}
+
+/// Water and timing of a step, volumes in L and times in min
+pub struct StepPlan {
+    pub water_added: f32,
+    /// °C of the water added, strike water for the first step
+    pub water_temp: Option<f32>,
+    pub total_water: f32,
+    /// Thick mash to pull and boil
+    pub decoction_vol: Option<f32>,
+    /// Time between the end of the previous rest and the start of this one
+    pub lead_time: u16,
+    pub start: u16,
+    /// Infusion water isn't hot enough to reach the step temperature
+    pub unreachable: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct MashSchedule {
+    pub grain_weight: f32,
+    pub mash_water_vol: f32,
+    grain_temp: f32,
+    /// °C of the water used for infusions after mashing in
+    infusion_temp: f32,
+    steps: Vec<MashStep>,
+    decoction: decoction::DecoctionSettings,
+}
+
+impl Default for MashSchedule {
+    fn default() -> Self {
+        Self {
+            grain_weight: 0.,
+            mash_water_vol: 0.,
+            grain_temp: 20.,
+            infusion_temp: 98.,
+            steps: vec![MashStep::default()],
+            decoction: Default::default(),
+        }
+    }
+}
+
+impl MashSchedule {
+    fn grain_mass_eq(&self) -> f32 {
+        self.grain_weight / 1000. * GRAIN_SPECIFIC_HEAT
+    }
+
+    /// Mash in with `mash_water_vol` then heat each step,
+    /// adding water for infusions or pulling thick mash for decoctions
+    ///
+    pub fn plan(&self) -> Vec<StepPlan> {
+        self.plan_for(self.mash_water_vol)
+    }
+
+    fn plan_for(&self, mash_water_vol: f32) -> Vec<StepPlan> {
+        let grain_mass_eq = self.grain_mass_eq();
+        let grain_vol = self.grain_weight / 1000. * math::GRAIN_DISPLACEMENT;
+        let mut plans: Vec<StepPlan> = vec![];
+        let mut total_water = mash_water_vol;
+        let mut mash_temp = self.grain_temp;
+        let mut rest_end = 0;
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let heating = index > 0 && step.temperature > mash_temp;
+            let mut plan = StepPlan {
+                water_added: 0.,
+                water_temp: None,
+                total_water,
+                decoction_vol: None,
+                lead_time: 0,
+                start: rest_end,
+                unreachable: false,
+            };
+
+            if index == 0 {
+                plan.water_added = mash_water_vol;
+                plan.water_temp = (mash_water_vol > 0.).then(|| {
+                    math::compute_strike_temperature(
+                        mash_water_vol,
+                        step.temperature,
+                        grain_mass_eq,
+                        self.grain_temp,
+                        0.,
+                        0.,
+                    )
+                });
+            } else if heating {
+                match step.step_type {
+                    StepType::Infusion if step.temperature < self.infusion_temp => {
+                        plan.water_added = math::compute_infusion_vol(
+                            mash_temp,
+                            step.temperature,
+                            self.infusion_temp,
+                            grain_mass_eq,
+                            total_water,
+                        );
+                        plan.water_temp = Some(self.infusion_temp);
+                    }
+                    StepType::Infusion => plan.unreachable = true,
+                    StepType::DirectHeat if self.decoction.heating_rate > 0. => {
+                        plan.lead_time = ((step.temperature - mash_temp)
+                            / self.decoction.heating_rate)
+                            .ceil() as u16;
+                    }
+                    StepType::DirectHeat => {}
+                    StepType::Decoction => {
+                        plan.decoction_vol = Some(math::compute_decoction_vol(
+                            mash_temp,
+                            step.temperature,
+                            decoction::BOIL_TEMP,
+                            total_water + grain_vol,
+                        ));
+                        plan.lead_time = self.decoction.lead_time(mash_temp);
+                    }
+                }
+            }
+
+            total_water += if index == 0 { 0. } else { plan.water_added };
+            plan.total_water = total_water;
+            plan.start = rest_end + plan.lead_time;
+
+            rest_end = plan.start + step.duration;
+            mash_temp = step.temperature;
+            plans.push(plan);
+        }
+
+        plans
+    }
+
+    /// Water added by infusions after mashing in, in L
+    ///
+    pub fn infusion_water_vol(&self) -> f32 {
+        self.infusion_water_vol_for(self.mash_water_vol)
+    }
+
+    fn infusion_water_vol_for(&self, mash_water_vol: f32) -> f32 {
+        self.plan_for(mash_water_vol)
+            .last()
+            .map_or(0., |plan| plan.total_water - mash_water_vol)
+    }
+
+    /// Infusion water as `slope * mash_water_vol + offset`, in L
+    /// Each infusion heats the grain and the water already there, so the total is affine
+    ///
+    pub fn infusion_water_coefficients(&self) -> (f32, f32) {
+        let offset = self.infusion_water_vol_for(0.);
+
+        (self.infusion_water_vol_for(1.) - offset, offset)
+    }
+
+    fn lager_steps() -> Vec<MashStep> {
+        [
+            ("Protéines", 52., 15),
+            ("Bêta-amylase", 63., 40),
+            ("Alpha-amylase", 72., 20),
+            ("Mash-out", 78., 10),
+        ]
+        .into_iter()
+        .map(|(name, temperature, duration)| MashStep {
+            name: name.to_string(),
+            temperature,
+            duration,
+            step_type: StepType::Infusion,
+        })
+        .collect()
+    }
+}
+
+impl super::AppModule for MashSchedule {
+    fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        if self.steps.is_empty() {
+            self.steps.push(MashStep::default());
+        }
+
+        ui.horizontal(|ui| {
+            ui.heading("Empâtage");
+            if ui.button("Ajouter un palier").clicked() {
+                let temperature = self.steps.last().map_or(66., |step| step.temperature + 5.);
+
+                self.steps.push(MashStep {
+                    temperature,
+                    ..Default::default()
+                });
+            }
+            if ui.button("Paliers lager").clicked() {
+                self.steps = Self::lager_steps();
+            }
+            for preset in decoction::Preset::ALL {
+                if ui.button(preset.label()).clicked() {
+                    self.steps = preset.steps();
+                }
+            }
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        egui::Frame::new()
+            .fill(LIGHTER_COLOR)
+            .inner_margin(DEFAULT_PADDING)
+            .corner_radius(DEFAULT_CORNER_RADIUS)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Température du grain (°C): ");
+                    ui.add(Slider::new(&mut self.grain_temp, -10.0..=40.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Température de l'eau d'infusion (°C): ");
+                    ui.add(Slider::new(&mut self.infusion_temp, 70.0..=100.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                self.decoction.show(ui);
+
+                ui.add_space(DEFAULT_SPACING);
+
+                let plans = self.plan();
+                let mut deleted = None;
+
+                Grid::new("paliers").striped(true).show(ui, |ui| {
+                    for title in [
+                        "Palier",
+                        "Type",
+                        "Température (°C)",
+                        "Durée (min)",
+                        "Eau ajoutée (L)",
+                        "Température de l'eau (°C)",
+                        "Eau totale (L)",
+                        "Décoction (L)",
+                        "",
+                    ] {
+                        ui.label(title);
+                    }
+                    ui.end_row();
+
+                    for (index, (step, plan)) in self.steps.iter_mut().zip(&plans).enumerate() {
+                        ui.add(TextEdit::singleline(&mut step.name).desired_width(100.));
+
+                        if index == 0 {
+                            ui.label("Empâtage");
+                        } else {
+                            ComboBox::from_id_salt(("palier-type", index))
+                                .selected_text(step.step_type.label())
+                                .show_ui(ui, |ui| {
+                                    for step_type in StepType::ALL {
+                                        ui.selectable_value(
+                                            &mut step.step_type,
+                                            step_type,
+                                            step_type.label(),
+                                        );
+                                    }
+                                });
+                        }
+
+                        ui.add(
+                            DragValue::new(&mut step.temperature)
+                                .speed(0.5)
+                                .range(20.0..=100.),
+                        );
+                        ui.add(DragValue::new(&mut step.duration).range(0..=300));
+                        if plan.unreachable {
+                            ui.colored_label(ERROR_COLOR, "impossible");
+                        } else {
+                            ui.label(format!("{:.2}", plan.water_added));
+                        }
+                        ui.label(
+                            plan.water_temp
+                                .map(|temp| format!("{temp:.1}"))
+                                .unwrap_or_default(),
+                        );
+                        ui.label(format!("{:.2}", plan.total_water));
+                        ui.label(
+                            plan.decoction_vol
+                                .map(|volume| format!("{volume:.2}"))
+                                .unwrap_or_default(),
+                        );
+
+                        if index > 0 && ui.button("x").clicked() {
+                            deleted = Some(index);
+                        }
+
+                        ui.end_row();
+                    }
+                });
+
+                for (step, _) in self
+                    .steps
+                    .iter()
+                    .zip(&plans)
+                    .filter(|(_, plan)| plan.unreachable)
+                {
+                    ui.colored_label(
+                        ERROR_COLOR,
+                        format!(
+                            "Palier « {} » : une infusion à {:.0} °C ne peut pas amener la maische à {:.1} °C",
+                            step.name, self.infusion_temp, step.temperature
+                        ),
+                    );
+                }
+
+                if let Some(index) = deleted {
+                    self.steps.remove(index);
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
+                let plans = self.plan();
+                let duration = plans
+                    .last()
+                    .zip(self.steps.last())
+                    .map_or(0, |(plan, step)| plan.start + step.duration);
+
+                ui.label(format!(
+                    "Durée totale (min): {duration}, eau d'infusion ajoutée (L): {:.2}",
+                    self.infusion_water_vol()
+                ));
+
+                ui.add_space(DEFAULT_SPACING);
+
+                CollapsingHeader::new("Planning")
+                    .show(ui, |ui| decoction::time_plan_ui(ui, &self.steps, &plans));
+            });
+    }
+}
@@ -1,3 +1,9 @@
+/// Volume taken by the grain in the mash, L/kg
+pub const GRAIN_DISPLACEMENT: f32 = 0.67;
+
+/// Wort contraction from boiling to pitching temperature
+pub const THERMAL_SHRINKAGE: f32 = 0.04;
+
 /// Computes alcohol in beverage
 /// expressed in %/vol
 ///
@@ -17,11 +23,47 @@ pub fn compute_total_extract(og: f32) -> f32 {
     (0.9974 / ((1.0 / og) - 0.00382) + 0.01) * 10.0
 }
 
-/// Takes into account apparel's efficiency
+/// Inverse of `compute_total_extract`
+/// Returns gravity in °P from sugar extract in g/L
+///
+pub fn convert_extract_to_plato(extract: f32) -> f32 {
+    if extract <= 0.1 {
+        return 0.0;
+    }
+
+    1.0 / (0.9974 / (extract / 10.0 - 0.01) + 0.00382)
+}
+
+/// Takes into account the share of the malt extract reaching the fermenter
 /// Returns soluble extract in malt expressed in g/L
 ///
-pub fn compute_per_malt_extractable(total_extract: f32, malt_ratio: u8, efficiency: u8) -> f32 {
-    (total_extract * (malt_ratio as f32 / 100.0)) / (efficiency as f32 / 100.0)
+pub fn compute_per_malt_extractable(total_extract: f32, malt_ratio: u8, efficiency: f32) -> f32 {
+    (total_extract * (malt_ratio as f32 / 100.0)) / (efficiency / 100.0)
+}
+
+/// Share of the potential extract collected in the kettle
+/// Returns mash efficiency in %
+///
+pub fn compute_mash_efficiency(conversion_efficiency: u8, lauter_efficiency: u8) -> f32 {
+    conversion_efficiency as f32 * lauter_efficiency as f32 / 100.0
+}
+
+/// Share of the kettle wort reaching the fermenter
+/// Returns kettle efficiency in %
+///
+pub fn compute_kettle_efficiency(batch_size: u16, cooled_post_boil_vol: f32) -> f32 {
+    if batch_size > 0 && cooled_post_boil_vol > 0.0 {
+        (batch_size as f32 / cooled_post_boil_vol * 100.0).min(100.0)
+    } else {
+        100.0
+    }
+}
+
+/// Share of the potential extract reaching the fermenter
+/// Returns brewhouse efficiency in %
+///
+pub fn compute_brewhouse_efficiency(mash_efficiency: f32, kettle_efficiency: f32) -> f32 {
+    mash_efficiency * kettle_efficiency / 100.0
 }
 
 /// Returns weight of specific malts in g
@@ -48,28 +90,69 @@ pub fn compute_mash_water_vol(grain_weight: f32, water_ratio: f32) -> f32 {
     grain_weight / 1000.0 * water_ratio
 }
 
-/// Returns water volume in L after the mashing process
-/// Taking into account grain retention (*0.8* - 1.1 grain weight)
+/// Returns start mash water volume in L when mashing without sparge
+/// All the pre ebullition volume plus what the grain and the mash tun hold back
 ///
-pub fn compute_post_mash_water_vol(mash_water_vol: f32, grain_weight: f32) -> f32 {
-    mash_water_vol - ((grain_weight / 1000.0) * 0.8)
+pub fn compute_full_volume_mash_water_vol(
+    pre_ebullition_water_vol: f32,
+    grain_weight: f32,
+    grain_absorption: f32,
+    mash_tun_dead_space: f32,
+) -> f32 {
+    pre_ebullition_water_vol + (grain_weight / 1000.0) * grain_absorption + mash_tun_dead_space
+}
+
+/// Returns water volume in L collected after the mashing process
+/// Taking into account grain absorption (L/kg) and the mash tun dead space
+///
+pub fn compute_post_mash_water_vol(
+    mash_water_vol: f32,
+    grain_weight: f32,
+    grain_absorption: f32,
+    mash_tun_dead_space: f32,
+) -> f32 {
+    mash_water_vol - (grain_weight / 1000.0) * grain_absorption - mash_tun_dead_space
 }
 
-/// Returns sparge water volume in L
-/// Takes into account the evaporation rate
+/// Returns hot wort volume in L at the end of the boil
+/// Enough to fill the fermenter once cooled and kettle dead space, trub and chiller took their share
 ///
-pub fn compute_sparge_water_vol(
+pub fn compute_post_boil_vol(
     batch_size: u16,
-    evaporation_rate: f32,
-    post_mash_water_vol: f32,
+    kettle_dead_space: f32,
+    trub_chiller_loss: f32,
 ) -> f32 {
-    (batch_size as f32 + (batch_size as f32 * (evaporation_rate / 100.0))) - post_mash_water_vol
+    (batch_size as f32 + kettle_dead_space + trub_chiller_loss) / (1.0 - THERMAL_SHRINKAGE)
 }
 
-/// Returns pre ebullition water volumen in L
+/// Returns pre ebullition water volume in L
+/// Boil-off rate in L/h, boil time in min
 ///
-pub fn compute_pre_ebullition_water_vol(sparge_water_vol: f32, post_mash_water_vol: f32) -> f32 {
-    sparge_water_vol + post_mash_water_vol
+pub fn compute_pre_ebullition_water_vol(
+    post_boil_vol: f32,
+    boil_off_rate: f32,
+    boil_time: u16,
+) -> f32 {
+    post_boil_vol + boil_off_rate * (boil_time as f32 / 60.0)
+}
+
+/// Returns sparge water volume in L, none when the mash already gives the whole volume
+///
+pub fn compute_sparge_water_vol(pre_ebullition_water_vol: f32, post_mash_water_vol: f32) -> f32 {
+    (pre_ebullition_water_vol - post_mash_water_vol).max(0.0)
+}
+
+/// Returns residual alkalinity in ppm as CaCO3
+/// Bicarbonate, calcium and magnesium in mg/L
+///
+pub fn compute_residual_alkalinity(bicarbonate: f32, calcium: f32, magnesium: f32) -> f32 {
+    convert_bicarbonate_to_alkalinity(bicarbonate) - (calcium / 1.4 + magnesium / 1.7)
+}
+
+/// Bicarbonate in mg/L to alkalinity in ppm as CaCO3
+///
+pub fn convert_bicarbonate_to_alkalinity(bicarbonate: f32) -> f32 {
+    bicarbonate * (50.0 / 61.0)
 }
 
 /// Malt's color contribution
@@ -244,3 +327,45 @@ pub fn compute_equilibrium_pressure(beer_temperature: f32, saturation_target: f3
 pub fn compute_temperature_after_mix(temp_a: f32, vol_a: f32, temp_b: f32, vol_b: f32) -> f32 {
     (temp_a * vol_a + temp_b * vol_b) / (vol_a + vol_b)
 }
+
+/// Returns strike water temperature in °C
+/// Grain and mash tun are water equivalent masses in kg (mass * specific heat)
+/// that the strike water has to bring to the target temperature
+///
+pub fn compute_strike_temperature(
+    mash_water_vol: f32,
+    target_temp: f32,
+    grain_mass_eq: f32,
+    grain_temp: f32,
+    tun_mass_eq: f32,
+    tun_temp: f32,
+) -> f32 {
+    target_temp
+        + (grain_mass_eq * (target_temp - grain_temp) + tun_mass_eq * (target_temp - tun_temp))
+            / mash_water_vol
+}
+
+/// Returns the volume in L of hot water to add to raise the mash to the target temperature
+/// Grain is a water equivalent mass in kg (mass * specific heat)
+///
+pub fn compute_infusion_vol(
+    mash_temp: f32,
+    target_temp: f32,
+    infusion_temp: f32,
+    grain_mass_eq: f32,
+    mash_water_vol: f32,
+) -> f32 {
+    (target_temp - mash_temp) * (grain_mass_eq + mash_water_vol) / (infusion_temp - target_temp)
+}
+
+/// Returns the mash volume in L to pull, boil and return to reach the target temperature
+/// Thick mash is assumed to hold heat like the whole mash, per liter
+///
+pub fn compute_decoction_vol(
+    mash_temp: f32,
+    target_temp: f32,
+    boil_temp: f32,
+    mash_vol: f32,
+) -> f32 {
+    (target_temp - mash_temp) / (boil_temp - mash_temp) * mash_vol
+}
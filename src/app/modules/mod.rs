@@ -7,11 +7,18 @@ use std::{marker, process::exit};
 
 pub mod base;
 pub mod bjcp_style_index;
+pub mod cost;
 pub mod equilibrium_pressure;
+pub mod equipment;
 pub mod fermentecibles;
 pub mod hops;
 pub mod ingredients_index;
+pub mod inventory;
+pub mod mash_schedule;
 pub mod math;
+pub mod range_filter;
+pub mod strike_temperature;
+pub mod style_conformance;
 pub mod temperature_after_mix;
 pub mod ui_defaults;
 pub mod water;
@@ -0,0 +1,57 @@
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+/// Optional bounds a (min, max) range has to overlap
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub struct RangeFilter {
+    pub min: Option<f32>,
+    pub max: Option<f32>,
+}
+
+impl RangeFilter {
+    pub fn is_active(&self) -> bool {
+        self.min.is_some() || self.max.is_some()
+    }
+
+    /// Inactive filters let everything through, active ones reject missing ranges
+    ///
+    pub fn overlaps(&self, range: Option<(f32, f32)>) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+
+        let Some((range_min, range_max)) = range else {
+            return false;
+        };
+
+        self.min.is_none_or(|min| range_max >= min) && self.max.is_none_or(|max| range_min <= max)
+    }
+
+    /// Returns true when one of the bounds changed
+    ///
+    pub fn show(&mut self, ui: &mut Ui, label: &str, speed: f64) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label(label);
+            changed |= bound_ui(ui, "min", &mut self.min, speed);
+            changed |= bound_ui(ui, "max", &mut self.max, speed);
+        });
+
+        changed
+    }
+}
+
+fn bound_ui(ui: &mut Ui, label: &str, bound: &mut Option<f32>, speed: f64) -> bool {
+    let mut enabled = bound.is_some();
+    let mut value = bound.unwrap_or_default();
+
+    let mut changed = ui.checkbox(&mut enabled, label).changed();
+    changed |= ui
+        .add_enabled(enabled, DragValue::new(&mut value).speed(speed))
+        .changed();
+
+    *bound = enabled.then_some(value);
+
+    changed
+}
@@ -0,0 +1,122 @@
+use crate::app::modules::math::compute_strike_temperature;
+use crate::app::modules::ui_defaults::*;
+use eframe::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct StrikeTemperature {
+    opened: bool,
+    pub grain_weight: f32,
+    pub mash_water_vol: f32,
+    grain_temp: f32,
+    grain_specific_heat: f32,
+    target_temp: f32,
+    tun_mass_eq: f32,
+    tun_temp: f32,
+    heat_loss: f32,
+}
+
+impl Default for StrikeTemperature {
+    fn default() -> Self {
+        Self {
+            opened: false,
+            grain_weight: 0.,
+            mash_water_vol: 0.,
+            grain_temp: 20.,
+            grain_specific_heat: 0.4,
+            target_temp: 66.,
+            tun_mass_eq: 0.,
+            tun_temp: 20.,
+            heat_loss: 0.,
+        }
+    }
+}
+
+impl StrikeTemperature {
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui) {
+        if ui.button("Température d'empâtage").clicked() {
+            self.opened = !self.opened;
+        };
+
+        egui::Window::new("Température d'empâtage")
+            .open(&mut self.opened)
+            .default_size([250., 250.])
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "Grain (kg): {:.2}, eau d'empâtage (L): {:.2}",
+                    self.grain_weight / 1000.,
+                    self.mash_water_vol
+                ));
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Température cible (°C): ");
+                    ui.add(Slider::new(&mut self.target_temp, 35.0..=80.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Température du grain (°C): ");
+                    ui.add(Slider::new(&mut self.grain_temp, -10.0..=40.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Chaleur spécifique du grain (cal/g.°C): ");
+                    ui.add(Slider::new(&mut self.grain_specific_heat, 0.3..=0.5));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Inertie de la cuve (L d'eau équivalents): ");
+                    ui.add(Slider::new(&mut self.tun_mass_eq, 0.0..=50.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Température de la cuve (°C): ");
+                    ui.add(Slider::new(&mut self.tun_temp, -10.0..=80.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                ui.horizontal(|ui| {
+                    ui.label("Pertes thermiques (°C): ");
+                    ui.add(Slider::new(&mut self.heat_loss, 0.0..=10.));
+                });
+
+                ui.add_space(DEFAULT_SPACING);
+
+                if self.mash_water_vol <= 0. {
+                    ui.label("Ajoutez des fermentescibles pour calculer le volume d'empâtage");
+                    return;
+                }
+
+                let strike_temperature = compute_strike_temperature(
+                    self.mash_water_vol,
+                    self.target_temp,
+                    self.grain_weight / 1000. * self.grain_specific_heat,
+                    self.grain_temp,
+                    self.tun_mass_eq,
+                    self.tun_temp,
+                ) + self.heat_loss;
+
+                ui.label(format!(
+                    "Température de l'eau d'empâtage (°C): {strike_temperature:.1}"
+                ));
+            });
+    }
+}
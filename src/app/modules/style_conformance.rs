@@ -0,0 +1,135 @@
+use crate::app::modules::bjcp_style_index::{StyleRange, StyleRanges};
+use crate::app::modules::math;
+use crate::app::modules::ui_defaults::*;
+use eframe::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+const GAUGE_WIDTH: f32 = 200.0;
+const GAUGE_HEIGHT: f32 = 14.0;
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct StyleConformance {
+    pub style_name: Option<String>,
+    #[serde(skip)]
+    pub ranges: StyleRanges,
+    pub original_gravity: f32,
+    pub final_gravity: f32,
+    pub abv: f32,
+    pub ibu: f32,
+    pub ebc: u8,
+}
+
+impl super::AppModule for StyleConformance {
+    fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    fn show(&mut self, ui: &mut Ui) {
+        ui.heading("Conformité au style");
+        ui.add_space(DEFAULT_SPACING);
+
+        egui::Frame::new()
+            .fill(LIGHTER_COLOR)
+            .inner_margin(DEFAULT_PADDING)
+            .corner_radius(DEFAULT_CORNER_RADIUS)
+            .show(ui, |ui| {
+                let Some(style_name) = &self.style_name else {
+                    ui.label("Aucun style choisi : sélectionnez-en un dans la liste BJCP");
+                    return;
+                };
+
+                ui.label(style_name);
+
+                ui.add_space(DEFAULT_SPACING);
+
+                range_gauge(
+                    ui,
+                    "Densité initiale (SG)",
+                    self.ranges.og,
+                    math::convert_plato_to_sg(self.original_gravity),
+                    3,
+                );
+
+                range_gauge(
+                    ui,
+                    "Densité finale (SG)",
+                    self.ranges.fg,
+                    math::convert_plato_to_sg(self.final_gravity),
+                    3,
+                );
+
+                range_gauge(ui, "Alcool (%)", self.ranges.abv, self.abv, 1);
+
+                range_gauge(ui, "IBU", self.ranges.ibu, self.ibu, 0);
+
+                range_gauge(
+                    ui,
+                    "Couleur (SRM)",
+                    self.ranges.srm,
+                    math::convert_ebc_to_srm(self.ebc),
+                    1,
+                );
+
+                range_gauge(
+                    ui,
+                    "Couleur (EBC)",
+                    self.ranges.srm.map(|srm| srm.map(math::convert_srm_to_ebc)),
+                    self.ebc as f32,
+                    0,
+                );
+            });
+    }
+}
+
+/// Draws the style's range on a horizontal bar
+/// with a marker where the recipe value falls
+///
+fn range_gauge(ui: &mut Ui, label: &str, range: Option<StyleRange>, value: f32, decimals: usize) {
+    ui.horizontal(|ui| {
+        ui.add_sized([140., GAUGE_HEIGHT], Label::new(label));
+
+        let Some(range) = range else {
+            ui.label("Non défini par le style");
+            return;
+        };
+
+        let StyleRange { min, max } = range;
+
+        let (rect, _) = ui.allocate_exact_size(vec2(GAUGE_WIDTH, GAUGE_HEIGHT), Sense::hover());
+
+        // Leave half the style's span as margin on each side, and always keep the marker visible
+        let margin = (range.width() / 2.0).max(f32::EPSILON);
+        let low = (min - margin).min(value);
+        let high = (max + margin).max(value);
+        let to_x = |v: f32| remap(v, low..=high, rect.left()..=rect.right());
+
+        let in_range = range.contains(value);
+        let value_color = if in_range {
+            ui.visuals().strong_text_color()
+        } else {
+            ERROR_COLOR
+        };
+
+        let painter = ui.painter();
+
+        painter.rect_filled(rect, CornerRadius::same(4), ui.visuals().extreme_bg_color);
+        painter.rect_filled(
+            Rect::from_x_y_ranges(to_x(min)..=to_x(max), rect.y_range()),
+            CornerRadius::same(4),
+            SUCCESS_COLOR,
+        );
+        painter.line_segment(
+            [
+                pos2(to_x(value), rect.top() - 2.0),
+                pos2(to_x(value), rect.bottom() + 2.0),
+            ],
+            Stroke::new(3.0, value_color),
+        );
+
+        ui.label(format!("{min:.decimals$} - {max:.decimals$}"));
+        ui.colored_label(value_color, format!("{value:.decimals$}"));
+    });
+}
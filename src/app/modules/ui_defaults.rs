@@ -3,6 +3,7 @@ use egui::*;
 
 pub const DEFAULT_SPACING: f32 = 8.0;
 pub const ERROR_COLOR: Color32 = Color32::from_rgb(255, 70, 70);
+pub const SUCCESS_COLOR: Color32 = Color32::from_rgb(70, 160, 90);
 pub const LIGHTER_COLOR: Color32 = Color32::from_rgb(40, 40, 40);
 pub const DEFAULT_CORNER_RADIUS: CornerRadius = CornerRadius::same(15);
 pub const DEFAULT_PADDING: Margin = Margin::same(15);
@@ -0,0 +1,197 @@
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+const LACTIC_PKA: f32 = 3.86;
+const CARBONIC_PKA: f32 = 6.35;
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum Acid {
+    Lactic80,
+    #[default]
+    Lactic88,
+    Phosphoric10,
+    Phosphoric75,
+    Phosphoric85,
+    /// About 2 % lactic acid by weight
+    AcidulatedMalt,
+}
+
+impl Acid {
+    const ALL: [Acid; 6] = [
+        Self::Lactic80,
+        Self::Lactic88,
+        Self::Phosphoric10,
+        Self::Phosphoric75,
+        Self::Phosphoric85,
+        Self::AcidulatedMalt,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Lactic80 => "Acide lactique 80 %",
+            Self::Lactic88 => "Acide lactique 88 %",
+            Self::Phosphoric10 => "Acide phosphorique 10 %",
+            Self::Phosphoric75 => "Acide phosphorique 75 %",
+            Self::Phosphoric85 => "Acide phosphorique 85 %",
+            Self::AcidulatedMalt => "Malt acide",
+        }
+    }
+
+    fn unit(&self) -> &'static str {
+        match self {
+            Self::AcidulatedMalt => "g",
+            _ => "mL",
+        }
+    }
+
+    /// mEq of acidity per mL (per g for acidulated malt) at a given pH
+    /// Lactic acid is only partly dissociated, phosphoric acid gives its first proton only
+    ///
+    pub fn meq_per_unit(&self, ph: f32) -> f32 {
+        let lactic_dissociation = 1. / (1. + 10_f32.powf(LACTIC_PKA - ph));
+
+        // concentration (%) * density (g/mL) * 1000 / molar mass (g/mol)
+        match self {
+            Self::Lactic80 => 0.80 * 1.184 * 1000. / 90.08 * lactic_dissociation,
+            Self::Lactic88 => 0.88 * 1.209 * 1000. / 90.08 * lactic_dissociation,
+            Self::Phosphoric10 => 0.10 * 1.053 * 1000. / 98.0,
+            Self::Phosphoric75 => 0.75 * 1.574 * 1000. / 98.0,
+            Self::Phosphoric85 => 0.85 * 1.685 * 1000. / 98.0,
+            Self::AcidulatedMalt => 0.02 * 1000. / 90.08 * lactic_dissociation,
+        }
+    }
+
+    /// mL or g needed to bring `meq` of acidity at the target pH
+    ///
+    pub fn dose(&self, meq: f32, ph: f32) -> f32 {
+        meq.max(0.) / self.meq_per_unit(ph)
+    }
+}
+
+/// Share of the alkalinity still present as bicarbonate at a given pH
+///
+fn bicarbonate_fraction(ph: f32) -> f32 {
+    1. / (1. + 10_f32.powf(CARBONIC_PKA - ph))
+}
+
+/// mEq of acid to bring water down to the target pH
+/// Alkalinity in ppm as CaCO3, volume in L
+///
+pub fn water_acid_meq(alkalinity: f32, volume: f32, source_ph: f32, target_ph: f32) -> f32 {
+    let source_fraction = bicarbonate_fraction(source_ph);
+
+    if source_fraction <= 0. {
+        return 0.;
+    }
+
+    alkalinity / 50. * volume * (source_fraction - bicarbonate_fraction(target_ph))
+        / source_fraction
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct AcidAdditions {
+    mash_acid: Acid,
+    target_mash_ph: f32,
+    sparge_acid: Acid,
+    source_ph: f32,
+    target_sparge_ph: f32,
+}
+
+impl Default for AcidAdditions {
+    fn default() -> Self {
+        Self {
+            mash_acid: Acid::Lactic88,
+            target_mash_ph: 5.4,
+            sparge_acid: Acid::Phosphoric10,
+            source_ph: 7.5,
+            target_sparge_ph: 5.8,
+        }
+    }
+}
+
+impl AcidAdditions {
+    /// Mash buffer in mEq/pH, sparge alkalinity in ppm as CaCO3
+    ///
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        estimated_mash_ph: Option<f32>,
+        mash_buffer: f32,
+        sparge_alkalinity: f32,
+        sparge_water_vol: f32,
+    ) {
+        ui.label("Empâtage");
+
+        ui.horizontal(|ui| {
+            acid_combo(ui, "acide-empatage", &mut self.mash_acid);
+            ui.label("pH cible : ");
+            ui.add(Slider::new(&mut self.target_mash_ph, 5.2..=5.5).fixed_decimals(2));
+        });
+
+        match estimated_mash_ph {
+            None => {
+                ui.label("pH d'empâtage inconnu : ajoutez des fermentescibles");
+            }
+            Some(ph) if ph <= self.target_mash_ph => {
+                ui.label(format!(
+                    "pH estimé {ph:.2} : pas d'acide nécessaire, ajoutez de l'alcalinité pour remonter"
+                ));
+            }
+            Some(ph) => {
+                let meq = mash_buffer * (ph - self.target_mash_ph);
+
+                ui.label(format!(
+                    "{:.1} {} ({meq:.1} mEq) pour passer de {ph:.2} à {:.2}",
+                    self.mash_acid.dose(meq, self.target_mash_ph),
+                    self.mash_acid.unit(),
+                    self.target_mash_ph
+                ));
+            }
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.label(format!("Rinçage ({sparge_water_vol:.1} L)"));
+
+        ui.horizontal(|ui| {
+            acid_combo(ui, "acide-rincage", &mut self.sparge_acid);
+            ui.label("pH de l'eau : ");
+            ui.add(
+                DragValue::new(&mut self.source_ph)
+                    .speed(0.05)
+                    .range(5.0..=9.5),
+            );
+            ui.label("pH cible : ");
+            ui.add(
+                DragValue::new(&mut self.target_sparge_ph)
+                    .speed(0.05)
+                    .range(5.0..=7.0),
+            );
+        });
+
+        let meq = water_acid_meq(
+            sparge_alkalinity,
+            sparge_water_vol,
+            self.source_ph,
+            self.target_sparge_ph,
+        );
+
+        ui.label(format!(
+            "Alcalinité (ppm CaCO3) : {sparge_alkalinity:.0}, {:.1} {} ({meq:.1} mEq)",
+            self.sparge_acid.dose(meq, self.target_sparge_ph),
+            self.sparge_acid.unit()
+        ));
+    }
+}
+
+fn acid_combo(ui: &mut Ui, id_salt: &str, acid: &mut Acid) {
+    ComboBox::from_id_salt(id_salt)
+        .selected_text(acid.label())
+        .show_ui(ui, |ui| {
+            for choice in Acid::ALL {
+                ui.selectable_value(acid, choice, choice.label());
+            }
+        });
+}
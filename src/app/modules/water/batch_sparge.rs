@@ -0,0 +1,126 @@
+use super::Water;
+use crate::app::modules::math;
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+
+/// A wort draining, volume in L and gravity in °P
+pub struct Running {
+    /// Water added to the mash before draining
+    pub addition: f32,
+    pub volume: f32,
+    pub gravity: f32,
+}
+
+/// Splits the sparge so that every running collects the same volume
+/// Water is added before the first draining when the mash alone gives too little wort
+///
+pub fn split_batch_sparge(
+    mash_liquor: f32,
+    retained: f32,
+    pre_ebullition_water_vol: f32,
+    sparge_additions: u8,
+    kettle_extract: f32,
+) -> Vec<Running> {
+    let runnings_count = sparge_additions as f32 + 1.;
+    let first_runnings = mash_liquor - retained;
+    let running_vol = (pre_ebullition_water_vol / runnings_count).max(first_runnings);
+    let sparge_vol = ((pre_ebullition_water_vol - running_vol) / sparge_additions as f32).max(0.);
+
+    let mut volumes = vec![(running_vol - first_runnings, running_vol)];
+    volumes.extend((0..sparge_additions).map(|_| (sparge_vol, sparge_vol)));
+
+    // Share of the mash extract in each running, the grain keeps its wort at the running strength
+    let mut in_mash = 1.;
+    let mut liquid = mash_liquor;
+    let mut shares = vec![];
+
+    for (index, (addition, volume)) in volumes.iter().enumerate() {
+        if index > 0 {
+            liquid = retained;
+        }
+        liquid += addition;
+
+        let share = if liquid > 0. {
+            in_mash * volume / liquid
+        } else {
+            0.
+        };
+        in_mash -= share;
+        shares.push(share);
+    }
+
+    let collected: f32 = shares.iter().sum();
+    let mash_extract = if collected > 0. {
+        kettle_extract / collected
+    } else {
+        0.
+    };
+
+    volumes
+        .into_iter()
+        .zip(shares)
+        .map(|((addition, volume), share)| Running {
+            addition,
+            volume,
+            gravity: if volume > 0. {
+                math::convert_extract_to_plato(mash_extract * share / volume)
+            } else {
+                0.
+            },
+        })
+        .collect()
+}
+
+impl Water {
+    pub fn batch_sparge_ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Nombre d'ajouts : ");
+            ui.selectable_value(&mut self.sparge_additions, 1, "1");
+            ui.selectable_value(&mut self.sparge_additions, 2, "2");
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let runnings = split_batch_sparge(
+            self.mash_water_vol + self.infusion_water_vol,
+            self.retained_vol(),
+            self.pre_ebullition_water_vol,
+            self.sparge_additions.clamp(1, 2),
+            self.kettle_extract(),
+        );
+
+        Grid::new("rincage-lots").striped(true).show(ui, |ui| {
+            for title in [
+                "Écoulement",
+                "Eau ajoutée (L)",
+                "Volume (L)",
+                "Densité (°P)",
+            ] {
+                ui.label(title);
+            }
+            ui.end_row();
+
+            for (index, running) in runnings.iter().enumerate() {
+                ui.label(format!("{}", index + 1));
+                ui.label(format!("{:.2}", running.addition));
+                ui.label(format!("{:.2}", running.volume));
+                ui.label(format!(
+                    "{:.1} ({:.3} SG)",
+                    running.gravity,
+                    math::convert_plato_to_sg(running.gravity)
+                ));
+                ui.end_row();
+            }
+        });
+
+        if let Some(first) = runnings.first() {
+            if first.addition > 0. {
+                ui.add_space(DEFAULT_SPACING);
+                ui.label(format!(
+                    "Ajoutez {:.2} L d'eau à la maische avant le premier écoulement",
+                    first.addition
+                ));
+            }
+        }
+    }
+}
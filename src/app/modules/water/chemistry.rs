@@ -0,0 +1,282 @@
+use crate::app::modules::math;
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+/// Ion concentrations in mg/L (ppm)
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct IonProfile {
+    pub calcium: f32,
+    pub magnesium: f32,
+    pub sodium: f32,
+    pub chloride: f32,
+    pub sulfate: f32,
+    pub bicarbonate: f32,
+}
+
+impl IonProfile {
+    pub const LABELS: [&'static str; 6] = ["Ca", "Mg", "Na", "Cl", "SO4", "HCO3"];
+
+    pub fn to_array(self) -> [f32; 6] {
+        [
+            self.calcium,
+            self.magnesium,
+            self.sodium,
+            self.chloride,
+            self.sulfate,
+            self.bicarbonate,
+        ]
+    }
+
+    pub fn from_array(ions: [f32; 6]) -> Self {
+        Self {
+            calcium: ions[0],
+            magnesium: ions[1],
+            sodium: ions[2],
+            chloride: ions[3],
+            sulfate: ions[4],
+            bicarbonate: ions[5],
+        }
+    }
+
+    fn ions_mut(&mut self) -> [&mut f32; 6] {
+        [
+            &mut self.calcium,
+            &mut self.magnesium,
+            &mut self.sodium,
+            &mut self.chloride,
+            &mut self.sulfate,
+            &mut self.bicarbonate,
+        ]
+    }
+
+    /// Adds `weight` g of a salt dissolved in `volume` L
+    ///
+    pub fn with_salt(self, salt: Salt, weight: f32, volume: f32) -> Self {
+        if volume <= 0. {
+            return self;
+        }
+
+        let mut ions = self.to_array();
+
+        for (ion, added) in ions.iter_mut().zip(salt.ions_per_gram().to_array()) {
+            *ion += added * weight / volume;
+        }
+
+        Self::from_array(ions)
+    }
+
+    pub fn sulfate_chloride_ratio(&self) -> Option<f32> {
+        (self.chloride > 0.).then(|| self.sulfate / self.chloride)
+    }
+
+    /// In ppm as CaCO3
+    ///
+    pub fn residual_alkalinity(&self) -> f32 {
+        math::compute_residual_alkalinity(self.bicarbonate, self.calcium, self.magnesium)
+    }
+
+    /// Editable ion grid, returns true when a value changed
+    ///
+    pub fn edit_ui(&mut self, ui: &mut Ui, id_salt: &str) -> bool {
+        let mut changed = false;
+
+        Grid::new(id_salt).show(ui, |ui| {
+            for label in Self::LABELS {
+                ui.label(format!("{label} (mg/L)"));
+            }
+            ui.end_row();
+
+            for ion in self.ions_mut() {
+                changed |= ui
+                    .add(DragValue::new(ion).speed(0.5).range(0.0..=f32::MAX))
+                    .changed();
+            }
+            ui.end_row();
+        });
+
+        changed
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum Salt {
+    Gypsum,
+    CalciumChloride,
+    Epsom,
+    SodiumChloride,
+    BakingSoda,
+}
+
+impl Salt {
+    pub const ALL: [Salt; 5] = [
+        Self::Gypsum,
+        Self::CalciumChloride,
+        Self::Epsom,
+        Self::SodiumChloride,
+        Self::BakingSoda,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Gypsum => "Gypse (CaSO4·2H2O)",
+            Self::CalciumChloride => "Chlorure de calcium (CaCl2·2H2O)",
+            Self::Epsom => "Sel d'Epsom (MgSO4·7H2O)",
+            Self::SodiumChloride => "Sel de table (NaCl)",
+            Self::BakingSoda => "Bicarbonate de soude (NaHCO3)",
+        }
+    }
+
+    /// mg of each ion brought by 1 g of salt, hence mg/L when dissolved in 1 L
+    ///
+    pub fn ions_per_gram(&self) -> IonProfile {
+        match self {
+            Self::Gypsum => IonProfile {
+                calcium: 232.8,
+                sulfate: 557.9,
+                ..Default::default()
+            },
+            Self::CalciumChloride => IonProfile {
+                calcium: 272.6,
+                chloride: 482.3,
+                ..Default::default()
+            },
+            Self::Epsom => IonProfile {
+                magnesium: 98.6,
+                sulfate: 389.6,
+                ..Default::default()
+            },
+            Self::SodiumChloride => IonProfile {
+                sodium: 393.4,
+                chloride: 606.6,
+                ..Default::default()
+            },
+            Self::BakingSoda => IonProfile {
+                sodium: 273.7,
+                bicarbonate: 726.3,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Salt weights in g, in `Salt::ALL` order
+pub type SaltWeights = [f32; 5];
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct WaterChemistry {
+    pub source: IonProfile,
+    pub mash_salts: SaltWeights,
+    pub sparge_salts: SaltWeights,
+}
+
+impl WaterChemistry {
+    /// Mash water profile, the one driving the mash pH
+    ///
+    pub fn mash_profile(&self, mash_water_vol: f32) -> IonProfile {
+        add_salts(self.source, &self.mash_salts, mash_water_vol)
+    }
+
+    pub fn sparge_profile(&self, sparge_water_vol: f32) -> IonProfile {
+        add_salts(self.source, &self.sparge_salts, sparge_water_vol)
+    }
+
+    /// Profile of all the brewing water once mash and sparge are mixed
+    ///
+    pub fn total_profile(&self, mash_water_vol: f32, sparge_water_vol: f32) -> IonProfile {
+        let mut salts = self.mash_salts;
+
+        for (salt, sparge) in salts.iter_mut().zip(self.sparge_salts) {
+            *salt += sparge;
+        }
+
+        add_salts(self.source, &salts, mash_water_vol + sparge_water_vol)
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, mash_water_vol: f32, sparge_water_vol: f32) {
+        ui.label("Eau de source");
+        self.source.edit_ui(ui, "eau-source");
+
+        ui.add_space(DEFAULT_SPACING);
+
+        Grid::new("eau-sels").striped(true).show(ui, |ui| {
+            ui.label("Sels (g)");
+            ui.label(format!("Empâtage ({mash_water_vol:.1} L)"));
+            ui.label(format!("Rinçage ({sparge_water_vol:.1} L)"));
+            ui.end_row();
+
+            for (index, salt) in Salt::ALL.iter().enumerate() {
+                ui.label(salt.label());
+                ui.add(
+                    DragValue::new(&mut self.mash_salts[index])
+                        .speed(0.05)
+                        .range(0.0..=f32::MAX),
+                );
+                ui.add(
+                    DragValue::new(&mut self.sparge_salts[index])
+                        .speed(0.05)
+                        .range(0.0..=f32::MAX),
+                );
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let mash = self.mash_profile(mash_water_vol);
+        let total = self.total_profile(mash_water_vol, sparge_water_vol);
+
+        Grid::new("eau-profils").striped(true).show(ui, |ui| {
+            ui.label("Profil (mg/L)");
+            for label in IonProfile::LABELS {
+                ui.label(label);
+            }
+            ui.end_row();
+
+            for (name, profile) in [
+                ("Source", self.source),
+                ("Empâtage", mash),
+                ("Total", total),
+            ] {
+                ui.label(name);
+                for ion in profile.to_array() {
+                    ui.label(format!("{ion:.0}"));
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        match total.sulfate_chloride_ratio() {
+            Some(ratio) => ui.label(format!(
+                "Ratio SO4:Cl : {ratio:.2} ({})",
+                describe_sulfate_chloride_ratio(ratio)
+            )),
+            None => ui.label("Ratio SO4:Cl : pas de chlorure"),
+        };
+    }
+}
+
+fn add_salts(source: IonProfile, weights: &SaltWeights, volume: f32) -> IonProfile {
+    Salt::ALL
+        .iter()
+        .zip(weights)
+        .fold(source, |profile, (salt, weight)| {
+            profile.with_salt(*salt, *weight, volume)
+        })
+}
+
+/// Usual reading of the ratio, from malty to bitter
+///
+fn describe_sulfate_chloride_ratio(ratio: f32) -> &'static str {
+    match ratio {
+        ratio if ratio < 0.4 => "très malté",
+        ratio if ratio < 0.8 => "malté",
+        ratio if ratio < 1.5 => "équilibré",
+        ratio if ratio < 3. => "amer",
+        _ => "très amer",
+    }
+}
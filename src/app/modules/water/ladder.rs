@@ -0,0 +1,223 @@
+use super::Water;
+use crate::app::modules::math;
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+
+/// A point of the brew day, volumes in L and gravity in °P
+pub struct LadderStep {
+    pub label: &'static str,
+    pub volume: f32,
+    /// Volume lost since the previous step, with what it went to
+    pub loss: Option<(f32, &'static str)>,
+    pub gravity: Option<f32>,
+}
+
+impl Water {
+    /// Extract in g collected in the kettle from the mash
+    /// Fermentecibles added to the boil never go through the mash
+    ///
+    pub fn kettle_extract(&self) -> f32 {
+        self.mash_potential_extract * self.mash_efficiency / 100.
+    }
+
+    /// Pre-boil gravity in °P from the mashed grist
+    ///
+    pub fn pre_boil_gravity(&self) -> f32 {
+        gravity(self.kettle_extract(), self.pre_ebullition_water_vol)
+    }
+
+    /// Beer left once the fermenter losses are taken out, in L
+    ///
+    pub fn packaged_vol(&self) -> f32 {
+        (self.batch_size as f32 - self.equipment.fermenter_loss).max(0.)
+    }
+
+    /// Volumes from strike water to packaging
+    /// The recipe batch size is the volume going into the fermenter
+    ///
+    pub fn ladder(&self) -> Vec<LadderStep> {
+        let equipment = &self.equipment;
+        let grain_kg = self.grain_weight / 1000.;
+        let mash_liquor = self.mash_water_vol + self.infusion_water_vol;
+        let retained = self.retained_vol();
+        let kettle_extract = self.kettle_extract();
+
+        // Wort left in the grain and the mash tun holds its share of the extract
+        let collected_share = if mash_liquor + self.sparge_water_vol > 0. {
+            1. - retained / (mash_liquor + self.sparge_water_vol)
+        } else {
+            1.
+        };
+        let first_runnings_gravity = if collected_share > 0. {
+            gravity(kettle_extract / collected_share, mash_liquor)
+        } else {
+            0.
+        };
+
+        let cooled = self.post_boil_vol * (1. - math::THERMAL_SHRINKAGE);
+        let fermenter = self.batch_size as f32;
+
+        vec![
+            LadderStep {
+                label: "Eau d'empâtage",
+                volume: mash_liquor,
+                loss: None,
+                gravity: None,
+            },
+            LadderStep {
+                label: "Maische",
+                volume: mash_liquor + grain_kg * math::GRAIN_DISPLACEMENT,
+                loss: None,
+                gravity: Some(first_runnings_gravity),
+            },
+            LadderStep {
+                label: "Premier moût",
+                volume: self.post_mash_water_vol,
+                loss: Some((retained, "absorption du grain et volume mort")),
+                gravity: Some(first_runnings_gravity),
+            },
+            LadderStep {
+                label: "Pré-ébullition",
+                volume: self.pre_ebullition_water_vol,
+                loss: None,
+                gravity: Some(self.pre_boil_gravity()),
+            },
+            LadderStep {
+                label: "Fin d'ébullition (chaud)",
+                volume: self.post_boil_vol,
+                loss: Some((
+                    self.pre_ebullition_water_vol - self.post_boil_vol,
+                    "évaporation",
+                )),
+                gravity: Some(self.original_gravity),
+            },
+            LadderStep {
+                label: "Fin d'ébullition (refroidi)",
+                volume: cooled,
+                loss: Some((self.post_boil_vol - cooled, "contraction thermique")),
+                gravity: Some(self.original_gravity),
+            },
+            LadderStep {
+                label: "Fermenteur",
+                volume: fermenter,
+                loss: Some((
+                    cooled - fermenter,
+                    "volume mort de la cuve, trub et refroidisseur",
+                )),
+                gravity: Some(self.original_gravity),
+            },
+            LadderStep {
+                label: "Conditionné",
+                volume: self.packaged_vol(),
+                loss: Some((equipment.fermenter_loss, "pertes dans le fermenteur")),
+                gravity: Some(self.final_gravity),
+            },
+        ]
+    }
+
+    pub fn ladder_ui(&self, ui: &mut Ui) {
+        Grid::new("volumes-brassage").striped(true).show(ui, |ui| {
+            for title in ["Étape", "Volume (L)", "Densité (°P)", "Pertes (L)"] {
+                ui.label(title);
+            }
+            ui.end_row();
+
+            for step in self.ladder() {
+                ui.label(step.label);
+                ui.label(format!("{:.2}", step.volume));
+                ui.label(
+                    step.gravity
+                        .map(|gravity| {
+                            format!(
+                                "{gravity:.1} ({:.3} SG)",
+                                math::convert_plato_to_sg(gravity)
+                            )
+                        })
+                        .unwrap_or_default(),
+                );
+                ui.label(
+                    step.loss
+                        .filter(|(volume, _)| *volume > 0.)
+                        .map(|(volume, reason)| format!("{volume:.2} ({reason})"))
+                        .unwrap_or_default(),
+                );
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.label("Le volume de la recette est celui qui entre dans le fermenteur");
+    }
+}
+
+fn gravity(extract: f32, volume: f32) -> f32 {
+    if volume > 0. {
+        math::convert_extract_to_plato(extract / volume)
+    } else {
+        0.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::modules::equipment::Equipment;
+    use crate::app::modules::AppModule;
+
+    fn water(equipment: Equipment) -> Water {
+        let mut water = Water::new();
+        water.batch_size = 30;
+        water.original_gravity = 12.;
+        water.grain_weight = 6000.;
+        water.equipment = equipment;
+        water.compute_volumes();
+        water
+    }
+
+    fn assert_balanced(water: &Water) {
+        let ladder = water.ladder();
+        let pre_boil = ladder
+            .iter()
+            .position(|step| step.label == "Pré-ébullition")
+            .unwrap();
+
+        // Strike water only loses what the grain and the mash tun keep
+        let first_runnings = &ladder[2];
+        let (retained, _) = first_runnings.loss.unwrap();
+        assert!((first_runnings.volume - (ladder[0].volume - retained)).abs() < 1e-3);
+
+        for pair in ladder[pre_boil..].windows(2) {
+            let (loss, _) = pair[1].loss.unwrap();
+
+            assert!(loss >= 0., "{} creates wort", pair[1].label);
+            assert!(
+                (pair[1].volume - (pair[0].volume - loss)).abs() < 1e-3,
+                "{} does not balance",
+                pair[1].label
+            );
+        }
+    }
+
+    #[test]
+    fn ladder_balances_without_losses() {
+        let water = water(Equipment::default());
+
+        assert_balanced(&water);
+        assert!((water.kettle_efficiency() - 100.).abs() < 1e-3);
+    }
+
+    #[test]
+    fn ladder_balances_with_losses() {
+        let water = water(Equipment {
+            mash_tun_dead_space: 1.,
+            kettle_dead_space: 1.5,
+            trub_chiller_loss: 1.,
+            fermenter_loss: 0.5,
+            ..Default::default()
+        });
+
+        assert_balanced(&water);
+        assert!(water.kettle_efficiency() < 100.);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::app::modules::fermentecibles::{MaltType, MashMalt};
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+
+/// pH of a malt mashed in distilled water
+/// Base malts get more acidic as they darken, crystal malts even more, roasted ones are flat
+///
+pub fn distilled_water_ph(malt_type: MaltType, ebc: u8) -> f32 {
+    match malt_type {
+        MaltType::Base => (5.75 - 0.013 * ebc as f32).max(5.3),
+        MaltType::Crystal => 5.22 - 0.0019 * ebc as f32,
+        MaltType::Roast => 4.7,
+    }
+}
+
+/// Malt buffering capacity in mEq/(kg.pH)
+///
+pub fn buffering_capacity(malt_type: MaltType) -> f32 {
+    match malt_type {
+        MaltType::Base => 40.,
+        MaltType::Crystal => 55.,
+        MaltType::Roast => 60.,
+    }
+}
+
+/// Buffering of the whole grist in mEq/pH
+///
+pub fn mash_buffer(malts: &[MashMalt]) -> f32 {
+    malts
+        .iter()
+        .map(|malt| malt.weight / 1000. * buffering_capacity(malt.malt_type))
+        .sum()
+}
+
+/// Mash pH where the water alkalinity balances the malts acidity
+/// Residual alkalinity in ppm as CaCO3, mash thickness in L/kg
+///
+pub fn estimate_mash_ph<'a>(
+    malts: impl Iterator<Item = &'a MashMalt>,
+    residual_alkalinity: f32,
+    mash_water_ratio: f32,
+) -> Option<f32> {
+    let mut buffer = 0.;
+    let mut weighted_ph = 0.;
+    let mut grain_weight = 0.;
+
+    for malt in malts.filter(|malt| malt.weight > 0.) {
+        let kg = malt.weight / 1000.;
+        let malt_buffer = kg * buffering_capacity(malt.malt_type);
+
+        buffer += malt_buffer;
+        weighted_ph += malt_buffer * distilled_water_ph(malt.malt_type, malt.ebc);
+        grain_weight += kg;
+    }
+
+    // 50 mg of CaCO3 per mEq
+    let water_alkalinity = residual_alkalinity / 50. * mash_water_ratio * grain_weight;
+
+    (buffer > 0.).then(|| (weighted_ph + water_alkalinity) / buffer)
+}
+
+pub fn mash_ph_ui(
+    ui: &mut Ui,
+    malts: &[MashMalt],
+    residual_alkalinity: f32,
+    mash_water_ratio: f32,
+) {
+    ui.label(format!(
+        "Alcalinité résiduelle (ppm CaCO3) : {residual_alkalinity:.0}"
+    ));
+
+    let Some(ph) = estimate_mash_ph(malts.iter(), residual_alkalinity, mash_water_ratio) else {
+        ui.label("Ajoutez des fermentescibles pour estimer le pH");
+        return;
+    };
+
+    ui.add_space(DEFAULT_SPACING);
+
+    let label = format!("pH d'empâtage estimé : {ph:.2}");
+
+    if (5.2..=5.6).contains(&ph) {
+        ui.colored_label(SUCCESS_COLOR, label);
+    } else {
+        ui.colored_label(ERROR_COLOR, format!("{label} (cible 5.2 - 5.6)"));
+    }
+
+    ui.add_space(DEFAULT_SPACING);
+
+    Grid::new("ph-malts").striped(true).show(ui, |ui| {
+        ui.label("Fermentescible");
+        ui.label("Type");
+        ui.label("pH eau distillée");
+        ui.label("Effet sur le pH");
+        ui.end_row();
+
+        for (index, malt) in malts.iter().enumerate() {
+            let others = malts
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, malt)| malt);
+
+            ui.label(&malt.name);
+            ui.label(malt.malt_type.label());
+            ui.label(format!(
+                "{:.2}",
+                distilled_water_ph(malt.malt_type, malt.ebc)
+            ));
+            match estimate_mash_ph(others, residual_alkalinity, mash_water_ratio) {
+                Some(without) => ui.label(format!("{:+.2}", ph - without)),
+                None => ui.label("-"),
+            };
+            ui.end_row();
+        }
+    });
+}
@@ -0,0 +1,58 @@
+use crate::app::modules::equipment::Equipment;
+use serde::{Deserialize, Serialize};
+
+/// Squeezing the bag leaves less wort in the grain
+const BIAB_SQUEEZED_ABSORPTION: f32 = 0.5;
+
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq)]
+pub enum MashingMethod {
+    FlySparge,
+    #[default]
+    BatchSparge,
+    NoSparge,
+    BiabSqueeze,
+}
+
+impl MashingMethod {
+    pub const ALL: [MashingMethod; 4] = [
+        Self::FlySparge,
+        Self::BatchSparge,
+        Self::NoSparge,
+        Self::BiabSqueeze,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::FlySparge => "Rinçage continu",
+            Self::BatchSparge => "Rinçage par lots",
+            Self::NoSparge => "Sans rinçage",
+            Self::BiabSqueeze => "BIAB (sac pressé)",
+        }
+    }
+
+    /// Without sparge, all the brewing water goes into the mash
+    ///
+    pub fn has_sparge(&self) -> bool {
+        matches!(self, Self::FlySparge | Self::BatchSparge)
+    }
+
+    /// L/kg
+    ///
+    pub fn grain_absorption(&self, equipment: &Equipment) -> f32 {
+        match self {
+            Self::BiabSqueeze => BIAB_SQUEEZED_ABSORPTION,
+            _ => equipment.grain_absorption,
+        }
+    }
+
+    /// Usual lauter efficiency in %, extract left in the grain grows without sparge
+    ///
+    pub fn default_efficiency(&self) -> u8 {
+        match self {
+            Self::FlySparge => 80,
+            Self::BatchSparge => 75,
+            Self::NoSparge => 65,
+            Self::BiabSqueeze => 70,
+        }
+    }
+}
@@ -1,26 +1,176 @@
+use crate::app::modules::equipment::Equipment;
+use crate::app::modules::fermentecibles::MashMalt;
 use crate::app::modules::math;
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
 use serde::{Deserialize, Serialize};
 
+pub mod acid;
+mod batch_sparge;
+pub mod chemistry;
+mod ladder;
+pub mod mash_ph;
+pub mod method;
+mod pre_boil;
+pub mod profiles;
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct Water {
     pub batch_size: u16,
     pub grain_weight: f32,
+    /// °P
+    #[serde(default)]
+    pub original_gravity: f32,
+    /// °P
+    #[serde(default)]
+    pub final_gravity: f32,
+    #[serde(skip)]
+    pub malts: Vec<MashMalt>,
+    /// Extract in g of the mashed grist at 100 % efficiency
+    #[serde(skip)]
+    pub mash_potential_extract: f32,
+    /// Extract in g of the fermentecibles added to the boil
+    #[serde(skip)]
+    pub boil_potential_extract: f32,
+    /// Recipe conversion times lauter efficiency, in %
+    #[serde(skip)]
+    pub mash_efficiency: f32,
+    /// Mash schedule infusions from the mash water, see `MashSchedule::infusion_water_coefficients`
+    #[serde(skip)]
+    pub infusion_water_coefficients: (f32, f32),
+    /// Water added by the mash schedule infusions, in L
+    #[serde(skip)]
+    infusion_water_vol: f32,
+    #[serde(skip)]
+    pub equipment: Equipment,
+    #[serde(default)]
+    method: method::MashingMethod,
+    #[serde(skip)]
+    method_changed: bool,
+    /// Batch sparge water additions, 1 or 2
+    #[serde(default = "default_sparge_additions")]
+    sparge_additions: u8,
     mash_water_ratio: f32,
-    evaporation_rate: f32,
+    /// min
+    #[serde(default = "default_boil_time")]
+    boil_time: u16,
     mash_water_vol: f32,
     post_mash_water_vol: f32,
+    #[serde(default)]
+    post_boil_vol: f32,
     sparge_water_vol: f32,
     pre_ebullition_water_vol: f32,
+    #[serde(default)]
+    chemistry: chemistry::WaterChemistry,
+    #[serde(default)]
+    acid: acid::AcidAdditions,
+    #[serde(default)]
+    profiles: profiles::WaterProfiles,
+    #[serde(default)]
+    pre_boil_check: pre_boil::PreBoilCheck,
+}
+
+fn default_boil_time() -> u16 {
+    60
+}
+
+fn default_sparge_additions() -> u8 {
+    1
+}
+
+impl Water {
+    /// L/kg, depends on the mashing method
+    ///
+    pub fn grain_absorption(&self) -> f32 {
+        self.method.grain_absorption(&self.equipment)
+    }
+
+    /// Wort held back by the grain and the mash tun dead space, in L
+    ///
+    pub fn retained_vol(&self) -> f32 {
+        self.grain_weight / 1000. * self.grain_absorption() + self.equipment.mash_tun_dead_space
+    }
+
+    /// Mash thickness in L/kg actually used, the whole water without sparge
+    ///
+    pub fn mash_thickness(&self) -> f32 {
+        if self.grain_weight > 0. {
+            (self.mash_water_vol + self.infusion_water_vol) / (self.grain_weight / 1000.)
+        } else {
+            self.mash_water_ratio
+        }
+    }
+
+    /// Default efficiency of the mashing method, once after it was picked
+    ///
+    pub fn take_efficiency_changed(&mut self) -> Option<u8> {
+        std::mem::take(&mut self.method_changed).then(|| self.method.default_efficiency())
+    }
+
+    /// In L
+    ///
+    pub fn mash_water_vol(&self) -> f32 {
+        self.mash_water_vol
+    }
+
+    /// From the fermenter volume back to the mash water
+    ///
+    fn compute_volumes(&mut self) {
+        self.post_boil_vol = math::compute_post_boil_vol(
+            self.batch_size,
+            self.equipment.kettle_dead_space,
+            self.equipment.trub_chiller_loss,
+        );
+
+        self.pre_ebullition_water_vol = math::compute_pre_ebullition_water_vol(
+            self.post_boil_vol,
+            self.equipment.boil_off_rate,
+            self.boil_time,
+        );
+
+        let (infusion_slope, infusion_offset) = self.infusion_water_coefficients;
+
+        // Without sparge, mash water and infusions together make the full volume
+        self.mash_water_vol = if self.method.has_sparge() {
+            math::compute_mash_water_vol(self.grain_weight, self.mash_water_ratio)
+        } else {
+            ((math::compute_full_volume_mash_water_vol(
+                self.pre_ebullition_water_vol,
+                self.grain_weight,
+                self.grain_absorption(),
+                self.equipment.mash_tun_dead_space,
+            ) - infusion_offset)
+                / (1. + infusion_slope))
+                .max(0.)
+        };
+
+        self.infusion_water_vol = infusion_slope * self.mash_water_vol + infusion_offset;
+
+        self.post_mash_water_vol = math::compute_post_mash_water_vol(
+            self.mash_water_vol + self.infusion_water_vol,
+            self.grain_weight,
+            self.grain_absorption(),
+            self.equipment.mash_tun_dead_space,
+        );
+
+        self.sparge_water_vol =
+            math::compute_sparge_water_vol(self.pre_ebullition_water_vol, self.post_mash_water_vol);
+    }
+
+    /// Mash, infusions and sparge water, in L
+    ///
+    pub fn total_water_vol(&self) -> f32 {
+        self.mash_water_vol + self.infusion_water_vol + self.sparge_water_vol
+    }
 }
 
 impl super::AppModule for Water {
     fn new() -> Self {
         Self {
             mash_water_ratio: 3.5,
-            evaporation_rate: 10.,
+            boil_time: default_boil_time(),
+            sparge_additions: default_sparge_additions(),
             ..Default::default()
         }
     }
@@ -35,19 +185,50 @@ impl super::AppModule for Water {
             .corner_radius(DEFAULT_CORNER_RADIUS)
             .show(ui, |ui| {
                 ui.horizontal(|ui| {
-                    ui.label("Ratio d'eau à l'empâtage (L/kg): ");
-                    ui.add(Slider::new(&mut self.mash_water_ratio, 0.0..=10.0));
+                    ui.label("Méthode : ");
+                    ComboBox::from_id_salt("methode-empatage")
+                        .selected_text(self.method.label())
+                        .show_ui(ui, |ui| {
+                            for method in method::MashingMethod::ALL {
+                                self.method_changed |= ui
+                                    .selectable_value(&mut self.method, method, method.label())
+                                    .changed();
+                            }
+                        });
                 });
 
                 ui.add_space(DEFAULT_SPACING);
 
+                if self.method.has_sparge() {
+                    ui.horizontal(|ui| {
+                        ui.label("Ratio d'eau à l'empâtage (L/kg): ");
+                        ui.add(Slider::new(&mut self.mash_water_ratio, 0.0..=10.0));
+                    });
+                } else {
+                    ui.label(format!(
+                        "Ratio d'eau à l'empâtage (L/kg): {:.2}",
+                        self.mash_thickness()
+                    ));
+                }
+
+                ui.add_space(DEFAULT_SPACING);
+
                 ui.horizontal(|ui| {
-                    ui.label("Taux d'évaporation (%): ");
-                    ui.add(Slider::new(&mut self.evaporation_rate, 0.0..=20.0));
+                    ui.label("Durée d'ébullition (min): ");
+                    ui.add(Slider::new(&mut self.boil_time, 0..=180));
                 });
 
                 ui.add_space(DEFAULT_SPACING);
 
+                ui.label(format!(
+                    "Équipement : {} ({:.1} L/h d'évaporation, absorption {:.2} L/kg)",
+                    self.equipment.name,
+                    self.equipment.boil_off_rate,
+                    self.grain_absorption()
+                ));
+
+                ui.add_space(DEFAULT_SPACING);
+
                 ui.label(format!(
                     "Volume d'eau à l'empatage (L): {:.3}",
                     self.mash_water_vol
@@ -55,6 +236,15 @@ impl super::AppModule for Water {
 
                 ui.add_space(DEFAULT_SPACING);
 
+                if self.infusion_water_vol > 0. {
+                    ui.label(format!(
+                        "Volume d'eau des infusions (L): {:.3}",
+                        self.infusion_water_vol
+                    ));
+
+                    ui.add_space(DEFAULT_SPACING);
+                }
+
                 ui.label(format!(
                     "Volume d'eau de rinçage (L): {:.3}",
                     self.sparge_water_vol
@@ -67,22 +257,67 @@ impl super::AppModule for Water {
                     self.pre_ebullition_water_vol
                 ));
 
-                self.mash_water_vol =
-                    math::compute_mash_water_vol(self.grain_weight, self.mash_water_ratio);
+                ui.add_space(DEFAULT_SPACING);
 
-                self.post_mash_water_vol =
-                    math::compute_post_mash_water_vol(self.mash_water_vol, self.grain_weight);
+                ui.label(format!(
+                    "Volume en fin d'ébullition (L): {:.3}",
+                    self.post_boil_vol
+                ));
 
-                self.sparge_water_vol = math::compute_sparge_water_vol(
-                    self.batch_size,
-                    self.evaporation_rate,
-                    self.post_mash_water_vol,
-                );
+                self.compute_volumes();
 
-                self.pre_ebullition_water_vol = math::compute_pre_ebullition_water_vol(
-                    self.sparge_water_vol,
-                    self.post_mash_water_vol,
-                );
+                ui.add_space(DEFAULT_SPACING);
+
+                CollapsingHeader::new("Volumes du brassage").show(ui, |ui| self.ladder_ui(ui));
+
+                CollapsingHeader::new("Densité pré-ébullition").show(ui, |ui| self.pre_boil_ui(ui));
+
+                if self.method == method::MashingMethod::BatchSparge {
+                    CollapsingHeader::new("Rinçage par lots")
+                        .show(ui, |ui| self.batch_sparge_ui(ui));
+                }
+
+                CollapsingHeader::new("Chimie de l'eau").show(ui, |ui| {
+                    self.chemistry
+                        .show(ui, self.mash_water_vol, self.sparge_water_vol)
+                });
+
+                CollapsingHeader::new("Profil cible").show(ui, |ui| {
+                    self.profiles.show(
+                        ui,
+                        &mut self.chemistry,
+                        self.mash_water_vol,
+                        self.sparge_water_vol,
+                    )
+                });
+
+                let residual_alkalinity = self
+                    .chemistry
+                    .mash_profile(self.mash_water_vol)
+                    .residual_alkalinity();
+
+                CollapsingHeader::new("pH d'empâtage").show(ui, |ui| {
+                    mash_ph::mash_ph_ui(ui, &self.malts, residual_alkalinity, self.mash_thickness())
+                });
+
+                CollapsingHeader::new("Acidification").show(ui, |ui| {
+                    let sparge_bicarbonate = self
+                        .chemistry
+                        .sparge_profile(self.sparge_water_vol)
+                        .bicarbonate;
+
+                    self.acid.show(
+                        ui,
+                        mash_ph::estimate_mash_ph(
+                            self.malts.iter(),
+                            residual_alkalinity,
+                            self.mash_thickness(),
+                        ),
+                        mash_ph::mash_buffer(&self.malts),
+                        math::convert_bicarbonate_to_alkalinity(sparge_bicarbonate),
+                        self.sparge_water_vol,
+                    )
+                });
             });
     }
 }
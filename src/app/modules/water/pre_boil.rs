@@ -0,0 +1,143 @@
+use super::Water;
+use crate::app::modules::math;
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+/// Share of dry malt extract and sugar that is extract
+const DME_EXTRACT: f32 = 0.95;
+const SUGAR_EXTRACT: f32 = 1.0;
+
+/// Brew day measurements taken before the boil
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct PreBoilCheck {
+    /// °P
+    measured_gravity: f32,
+    /// L
+    measured_vol: f32,
+}
+
+impl Water {
+    /// Share of the kettle wort reaching the fermenter, in %
+    ///
+    pub fn kettle_efficiency(&self) -> f32 {
+        math::compute_kettle_efficiency(
+            self.batch_size,
+            self.post_boil_vol * (1. - math::THERMAL_SHRINKAGE),
+        )
+    }
+
+    pub fn pre_boil_ui(&mut self, ui: &mut Ui) {
+        let predicted = self.pre_boil_gravity();
+
+        ui.label(format!(
+            "Densité pré-ébullition prévue (°P): {predicted:.1} ({:.3} SG) pour {:.1} L",
+            math::convert_plato_to_sg(predicted),
+            self.pre_ebullition_water_vol
+        ));
+
+        ui.add_space(DEFAULT_SPACING);
+
+        let kettle_efficiency = self.kettle_efficiency();
+        let check = &mut self.pre_boil_check;
+
+        ui.horizontal(|ui| {
+            ui.label("Densité mesurée (°P): ");
+            ui.add(
+                DragValue::new(&mut check.measured_gravity)
+                    .speed(0.1)
+                    .range(0.0..=30.),
+            );
+            ui.label("Volume mesuré (L): ");
+            ui.add(
+                DragValue::new(&mut check.measured_vol)
+                    .speed(0.1)
+                    .range(0.0..=f32::MAX),
+            );
+        });
+
+        if check.measured_gravity <= 0.
+            || check.measured_vol <= 0.
+            || self.mash_potential_extract <= 0.
+        {
+            return;
+        }
+
+        let measured_extract =
+            math::compute_total_extract(check.measured_gravity) * check.measured_vol;
+        let mash_efficiency = measured_extract / self.mash_potential_extract * 100.;
+        let brewhouse_efficiency =
+            math::compute_brewhouse_efficiency(mash_efficiency, kettle_efficiency);
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.label(format!(
+            "Efficacité d'empâtage réelle (%): {mash_efficiency:.1} (prévue {:.1})",
+            self.mash_efficiency
+        ));
+        ui.label(format!(
+            "Efficacité du brassage réelle (%): {brewhouse_efficiency:.1} (prévue {:.1})",
+            math::compute_brewhouse_efficiency(self.mash_efficiency, kettle_efficiency)
+        ));
+
+        ui.add_space(DEFAULT_SPACING);
+
+        // Expected end of boil, in cooled liters
+        let boil_off = self.equipment.boil_off_rate * (self.boil_time as f32 / 60.);
+        let cooled_vol = (check.measured_vol - boil_off) * (1. - math::THERMAL_SHRINKAGE);
+
+        if cooled_vol <= 0. {
+            ui.colored_label(
+                ERROR_COLOR,
+                "Volume mesuré trop faible pour l'ébullition prévue",
+            );
+            return;
+        }
+
+        // Sugars and extracts added to the boil
+        let kettle_extract = measured_extract + self.boil_potential_extract;
+        let expected_og = math::convert_extract_to_plato(kettle_extract / cooled_vol);
+        let target_extract = math::compute_total_extract(self.original_gravity);
+        let target_vol = kettle_extract / target_extract;
+
+        ui.label(format!(
+            "Densité initiale attendue (°P): {expected_og:.1}, cible {:.1}",
+            self.original_gravity
+        ));
+
+        if (expected_og - self.original_gravity).abs() < 0.2 {
+            ui.colored_label(SUCCESS_COLOR, "Aucune correction nécessaire");
+        } else if expected_og < self.original_gravity {
+            let extra_boil = (cooled_vol - target_vol)
+                / (1. - math::THERMAL_SHRINKAGE)
+                / self.equipment.boil_off_rate
+                * 60.;
+            let missing_extract = target_extract * cooled_vol - kettle_extract;
+
+            ui.colored_label(ERROR_COLOR, "Densité trop faible, au choix :");
+            if self.equipment.boil_off_rate > 0. {
+                ui.label(format!(
+                    "- bouillir {extra_boil:.0} min de plus ({:.1} L dans le fermenteur)",
+                    target_vol * kettle_efficiency / 100.
+                ));
+            }
+            ui.label(format!(
+                "- ajouter {:.0} g d'extrait de malt sec",
+                missing_extract / DME_EXTRACT
+            ));
+            ui.label(format!(
+                "- ajouter {:.0} g de sucre",
+                missing_extract / SUGAR_EXTRACT
+            ));
+        } else {
+            ui.colored_label(
+                ERROR_COLOR,
+                format!(
+                    "Densité trop élevée : ajouter {:.1} L d'eau en fin d'ébullition",
+                    target_vol - cooled_vol
+                ),
+            );
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use super::chemistry::{IonProfile, Salt, SaltWeights, WaterChemistry};
+use crate::app::modules::ui_defaults::*;
+use egui::*;
+use serde::{Deserialize, Serialize};
+
+const SOLVER_ITERATIONS: usize = 500;
+
+#[derive(Deserialize, Serialize, Default, Clone)]
+#[serde(default)]
+pub struct TargetProfile {
+    pub name: String,
+    pub ions: IonProfile,
+}
+
+/// Classic brewing waters, in mg/L
+///
+fn builtin_profiles() -> Vec<TargetProfile> {
+    [
+        ("Burton", [295., 45., 55., 25., 725., 300.]),
+        ("Pilsen", [7., 2., 2., 5., 5., 15.]),
+        ("Dublin", [118., 4., 12., 19., 54., 319.]),
+        ("Munich", [77., 17., 4., 8., 18., 295.]),
+        ("Vienne", [200., 60., 8., 12., 125., 120.]),
+    ]
+    .into_iter()
+    .map(|(name, ions)| TargetProfile {
+        name: name.to_string(),
+        ions: IonProfile::from_array(ions),
+    })
+    .collect()
+}
+
+/// Salt concentrations in g/L bringing `source` closest to `target`
+/// Non-negative least squares on the ions, solved by projected coordinate descent
+///
+pub fn optimize_salts(source: IonProfile, target: IonProfile) -> SaltWeights {
+    let columns: Vec<[f32; 6]> = Salt::ALL
+        .iter()
+        .map(|salt| salt.ions_per_gram().to_array())
+        .collect();
+    let source = source.to_array();
+    let target = target.to_array();
+
+    let mut weights: SaltWeights = Default::default();
+    let mut residual: [f32; 6] = std::array::from_fn(|ion| source[ion] - target[ion]);
+
+    for _ in 0..SOLVER_ITERATIONS {
+        for (weight, column) in weights.iter_mut().zip(&columns) {
+            let norm: f32 = column.iter().map(|ion| ion * ion).sum();
+            let gradient: f32 = column.iter().zip(&residual).map(|(a, r)| a * r).sum();
+            let updated = (*weight - gradient / norm).max(0.);
+            let step = updated - *weight;
+
+            for (r, a) in residual.iter_mut().zip(column) {
+                *r += a * step;
+            }
+
+            *weight = updated;
+        }
+    }
+
+    weights
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct WaterProfiles {
+    /// Index in the builtin profiles followed by the saved ones
+    selected: usize,
+    saved: Vec<TargetProfile>,
+    new_name: String,
+}
+
+impl WaterProfiles {
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        chemistry: &mut WaterChemistry,
+        mash_water_vol: f32,
+        sparge_water_vol: f32,
+    ) {
+        let builtins = builtin_profiles();
+        let builtins_count = builtins.len();
+        let profiles: Vec<TargetProfile> = builtins.into_iter().chain(self.saved.clone()).collect();
+
+        self.selected = self.selected.min(profiles.len() - 1);
+
+        let mut deleted = None;
+
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("eau-profil-cible")
+                .selected_text(&profiles[self.selected].name)
+                .show_ui(ui, |ui| {
+                    for (index, profile) in profiles.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected, index, &profile.name);
+                    }
+                });
+
+            if self.selected >= builtins_count && ui.button("Supprimer").clicked() {
+                deleted = Some(self.selected - builtins_count);
+            }
+        });
+
+        // Saved targets can be typed in, a published water for instance
+        let saved = self
+            .selected
+            .checked_sub(builtins_count)
+            .and_then(|index| self.saved.get_mut(index));
+        let target = match saved {
+            Some(saved) => {
+                ui.add_space(DEFAULT_SPACING);
+                saved.ions.edit_ui(ui, "eau-profil-cible-edition");
+                saved.ions
+            }
+            None => profiles[self.selected].ions,
+        };
+        let volume = mash_water_vol + sparge_water_vol;
+        let concentrations = optimize_salts(chemistry.source, target);
+        let reached = Salt::ALL
+            .iter()
+            .zip(concentrations)
+            .fold(chemistry.source, |profile, (salt, concentration)| {
+                profile.with_salt(*salt, concentration, 1.)
+            });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        Grid::new("eau-optimisation").striped(true).show(ui, |ui| {
+            ui.label("mg/L");
+            for label in IonProfile::LABELS {
+                ui.label(label);
+            }
+            ui.end_row();
+
+            for (name, profile) in [("Cible", target), ("Atteint", reached)] {
+                ui.label(name);
+                for ion in profile.to_array() {
+                    ui.label(format!("{ion:.0}"));
+                }
+                ui.end_row();
+            }
+
+            ui.label("Écart");
+            for (reached, target) in reached.to_array().iter().zip(target.to_array()) {
+                let error = reached - target;
+                let text = format!("{error:+.0}");
+
+                if error.abs() > (target * 0.1).max(5.) {
+                    ui.colored_label(ERROR_COLOR, text);
+                } else {
+                    ui.label(text);
+                }
+            }
+            ui.end_row();
+        });
+
+        ui.add_space(DEFAULT_SPACING);
+
+        Grid::new("eau-sels-optimises").show(ui, |ui| {
+            for (salt, concentration) in Salt::ALL.iter().zip(concentrations) {
+                ui.label(salt.label());
+                ui.label(format!("{:.2} g", concentration * volume));
+                ui.end_row();
+            }
+        });
+
+        if ui
+            .button("Appliquer (réparti entre empâtage et rinçage)")
+            .clicked()
+        {
+            for (index, concentration) in concentrations.iter().enumerate() {
+                chemistry.mash_salts[index] = concentration * mash_water_vol;
+                chemistry.sparge_salts[index] = concentration * sparge_water_vol;
+            }
+        }
+
+        ui.add_space(DEFAULT_SPACING);
+
+        ui.horizontal(|ui| {
+            ui.label("Nom : ");
+            ui.text_edit_singleline(&mut self.new_name);
+
+            let named = !self.new_name.trim().is_empty();
+            let mut ions = None;
+
+            if ui
+                .add_enabled(
+                    named,
+                    Button::new("Enregistrer le profil total comme cible"),
+                )
+                .clicked()
+            {
+                ions = Some(chemistry.total_profile(mash_water_vol, sparge_water_vol));
+            }
+
+            if ui
+                .add_enabled(named, Button::new("Nouvelle cible à saisir"))
+                .clicked()
+            {
+                ions = Some(IonProfile::default());
+            }
+
+            if let Some(ions) = ions {
+                self.saved.push(TargetProfile {
+                    name: std::mem::take(&mut self.new_name).trim().to_string(),
+                    ions,
+                });
+                self.selected = builtins_count + self.saved.len() - 1;
+            }
+        });
+
+        if let Some(index) = deleted {
+            self.saved.remove(index);
+            self.selected = 0;
+        }
+    }
+}
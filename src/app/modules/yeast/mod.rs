@@ -1,9 +1,15 @@
+use crate::app::modules::ingredients_index::yeasts_index::YeastIng;
+use crate::app::modules::ingredients_index::{IngredientKind, IngredientUse};
 use crate::app::modules::math;
 use crate::app::modules::ui_defaults::*;
 use eframe::*;
 use egui::*;
 use serde::{Deserialize, Serialize};
 
+/// Cells per gram (millions) assumed for a ferment picked from the index
+const DRY_CELLS_PER_GRAM: u32 = 10_000;
+const LIQUID_CELLS_PER_GRAM: u32 = 1_000;
+
 #[derive(Deserialize, Serialize, Default)]
 struct Ferment {
     name: String,
@@ -11,6 +17,42 @@ struct Ferment {
     cells_per_gram: u32,
     pitch_weight: f64,
     ratio: u8,
+    form: Option<String>,
+    flocculation: Option<String>,
+    alcohol_tolerance: Option<f32>,
+    temp_min: Option<u32>,
+    temp_max: Option<u32>,
+}
+
+impl From<&YeastIng> for Ferment {
+    fn from(yeast: &YeastIng) -> Self {
+        let attenuation = match (yeast.attenuation_min, yeast.attenuation_max) {
+            (Some(min), Some(max)) => (min + max) / 2,
+            (Some(attenuation), None) | (None, Some(attenuation)) => attenuation,
+            (None, None) => 0,
+        };
+
+        let is_dry = yeast
+            .form
+            .as_ref()
+            .is_some_and(|form| form.to_lowercase().contains("dry"));
+
+        Self {
+            name: yeast.name.clone(),
+            attenuation: attenuation.min(100) as u8,
+            cells_per_gram: if is_dry {
+                DRY_CELLS_PER_GRAM
+            } else {
+                LIQUID_CELLS_PER_GRAM
+            },
+            form: yeast.form.clone(),
+            flocculation: yeast.flocculation.clone(),
+            alcohol_tolerance: yeast.alcohol_tolerance,
+            temp_min: yeast.temp_min,
+            temp_max: yeast.temp_max,
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Default)]
@@ -20,6 +62,21 @@ pub struct Yeast {
     cell_count: u64,
     ferments: Vec<Ferment>,
     pub max_attenuation: u8,
+    #[serde(skip)]
+    pub yeasts_index: Vec<YeastIng>,
+}
+
+impl Yeast {
+    pub fn ingredient_uses(&self) -> Vec<IngredientUse> {
+        self.ferments
+            .iter()
+            .map(|ferment| IngredientUse {
+                kind: IngredientKind::Yeast,
+                name: ferment.name.clone(),
+                weight: ferment.pitch_weight as f32,
+            })
+            .collect()
+    }
 }
 
 impl super::AppModule for Yeast {
@@ -53,7 +110,7 @@ impl super::AppModule for Yeast {
         let mut ratios = vec![];
 
         for (index, ferment) in self.ferments.iter_mut().enumerate() {
-            ferment_ui(ui, index, ferment);
+            ferment_ui(ui, index, ferment, &self.yeasts_index);
 
             ferment.pitch_weight =
                 math::compute_pitch_weight(self.cell_count, ferment.cells_per_gram, ferment.ratio);
@@ -79,7 +136,7 @@ impl super::AppModule for Yeast {
     }
 }
 
-fn ferment_ui(ui: &mut Ui, index: usize, ferment: &mut Ferment) {
+fn ferment_ui(ui: &mut Ui, index: usize, ferment: &mut Ferment, yeasts_index: &[YeastIng]) {
     Window::new(format!("Ferment {}", index + 1))
         .default_size([250., 250.])
         .show(ui.ctx(), |ui| {
@@ -90,6 +147,35 @@ fn ferment_ui(ui: &mut Ui, index: usize, ferment: &mut Ferment) {
                 .show(ui, |ui| {
                     ui.text_edit_singleline(&mut ferment.name);
                     ui.add_space(DEFAULT_SPACING);
+                    ComboBox::from_id_salt(format!("ferment-picker-{index}"))
+                        .selected_text("Choisir dans la liste")
+                        .show_ui(ui, |ui| {
+                            for yeast in yeasts_index {
+                                if ui.selectable_label(false, &yeast.name).clicked() {
+                                    *ferment = Ferment {
+                                        ratio: ferment.ratio,
+                                        ..Ferment::from(yeast)
+                                    };
+                                }
+                            }
+                        });
+                    ui.add_space(DEFAULT_SPACING);
+                    if let Some(form) = &ferment.form {
+                        ui.label(format!("Forme : {form}"));
+                        ui.add_space(DEFAULT_SPACING);
+                    }
+                    if let Some(flocculation) = &ferment.flocculation {
+                        ui.label(format!("Floculation : {flocculation}"));
+                        ui.add_space(DEFAULT_SPACING);
+                    }
+                    if let Some(alcohol_tolerance) = &ferment.alcohol_tolerance {
+                        ui.label(format!("Tolérance à l'alcool (%) : {alcohol_tolerance}"));
+                        ui.add_space(DEFAULT_SPACING);
+                    }
+                    if let (Some(temp_min), Some(temp_max)) = (ferment.temp_min, ferment.temp_max) {
+                        ui.label(format!("Température (°C) : {temp_min} - {temp_max}"));
+                        ui.add_space(DEFAULT_SPACING);
+                    }
                     ui.label("Atténuation (%)");
                     ui.add(Slider::new(&mut ferment.attenuation, 0..=100));
                     ui.add_space(DEFAULT_SPACING);